@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::builder::{BuildMode, Builder};
+use crate::error::Error;
+use crate::expr::Expr;
+use crate::flavor::{default_flavor, Flavor};
+use crate::value::{ToSqlValue, Value};
+
+// Args 结构体，用于管理 SQL 参数
+// 添加参数时返回形如 $0 或 ${name} 的占位标记，编译时再替换为对应方言的占位符
+#[derive(Clone)]
+pub struct Args {
+    pub(crate) flavor: Flavor,
+    pub(crate) mode: BuildMode,
+    // 参数超过方言的上限后重新编译时为 true，IN 列表尽量编码为一个 JSON 参数，见 in_list::rewrite
+    pub(crate) pack_in_lists: bool,
+    args: Vec<Arg>,
+    named_args: HashMap<String, usize>,
+}
+
+// 占位标记引用的参数
+#[derive(Clone)]
+enum Arg {
+    Value(Value),
+    // 编译时按目标 Flavor 渲染的表达式
+    Expr(Expr),
+    // 编译时按目标 Flavor 构造的子查询
+    Builder(Arc<dyn Builder + Send + Sync>),
+}
+
+// 编译时遇到的占位标记
+// 命名参数的名字和子查询的下标只在 MySQL 的命名参数中使用
+#[cfg_attr(not(any(feature = "mysql", feature = "mysql_async")), allow(dead_code))]
+pub(crate) enum Marker<'a> {
+    // 参数值，命名参数带有名字
+    Value(Option<&'a str>, &'a Value),
+    // 子查询，index 为参数的下标
+    Builder(usize, &'a dyn Builder),
+}
+
+impl Args {
+    // 创建一个新的 Args 实例，使用当前生效的默认 Flavor
+    pub fn new() -> Self {
+        Self {
+            flavor: default_flavor(),
+            mode: BuildMode::Lenient,
+            pack_in_lists: false,
+            args: Vec::new(),
+            named_args: HashMap::new(),
+        }
+    }
+
+    // 添加参数并返回占位标记
+    pub fn add(&mut self, arg: impl ToSqlValue) -> String {
+        self.push(Arg::Value(arg.to_sql_value()))
+    }
+
+    // 添加表达式并返回占位标记
+    // 表达式在编译时才按目标 Flavor 渲染，其中的参数按出现顺序编号
+    pub fn add_expr(&mut self, expr: impl Into<Expr>) -> String {
+        self.push(Arg::Expr(expr.into()))
+    }
+
+    // 添加子查询并返回占位标记，编译时使用同一个 Flavor 构造子查询
+    pub(crate) fn add_builder(&mut self, builder: Arc<dyn Builder + Send + Sync>) -> String {
+        self.push(Arg::Builder(builder))
+    }
+
+    fn push(&mut self, arg: Arg) -> String {
+        let idx = self.args.len();
+        self.args.push(arg);
+        format!("${}", idx)
+    }
+
+    // 添加命名参数并返回形如 ${name} 的占位标记
+    // 同名参数只保存一次，再次添加会覆盖之前的值
+    pub fn add_named(&mut self, name: &str, arg: impl ToSqlValue) -> String {
+        match self.named_args.get(name) {
+            Some(&idx) => self.args[idx] = Arg::Value(arg.to_sql_value()),
+            None => {
+                self.named_args.insert(name.to_string(), self.args.len());
+                self.args.push(Arg::Value(arg.to_sql_value()));
+            }
+        }
+
+        format!("${{{}}}", name)
+    }
+
+    // 返回已添加的参数数量
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    // 判断是否没有添加任何参数
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    // 使用当前的 Flavor 编译 SQL
+    pub fn compile(&self, format: &str) -> Result<(String, Vec<Value>), Error> {
+        self.compile_with_flavor(format, self.flavor.clone(), Vec::new())
+    }
+
+    // 使用指定的 Flavor 编译 SQL
+    // $n 和 ${name} 会被替换为方言的占位符并按出现顺序收集参数，$$ 会被替换为 $
+    // $n 引用的参数不存在时返回 MissingArgs，参数超过方言允许的数量时返回 TooManyArgs
+    // 参数超过上限时，先将 IN 列表编码为 JSON 参数重新编译一次，仍然超过时才返回 TooManyArgs
+    // 表达式和子查询使用同一个 Flavor 展开，参数接着已有的参数编号
+    pub fn compile_with_flavor(
+        &self,
+        format: &str,
+        flavor: Flavor,
+        initial_arg: Vec<Value>,
+    ) -> Result<(String, Vec<Value>), Error> {
+        let initial_len = initial_arg.len();
+        let (sql, mut values) = self.compile_values(format, &flavor, initial_arg)?;
+
+        let Some(max) = flavor.limits().max_params.filter(|max| values.len() > *max) else {
+            return Ok((sql, values));
+        };
+
+        if !self.pack_in_lists {
+            // 子查询返回的参数以传入的参数开头，截断后就是原来的 initial_arg
+            values.truncate(initial_len);
+
+            let packed = Args {
+                pack_in_lists: true,
+                ..self.clone()
+            };
+            let (sql, values) = packed.compile_values(format, &flavor, values)?;
+
+            if values.len() > max {
+                return Err(Error::TooManyArgs { count: values.len(), max });
+            }
+
+            return Ok((sql, values));
+        }
+
+        Err(Error::TooManyArgs { count: values.len(), max })
+    }
+
+    fn compile_values(&self, format: &str, flavor: &Flavor, initial_arg: Vec<Value>) -> Result<(String, Vec<Value>), Error> {
+        let mut values = initial_arg;
+        let sql = self.compile_markers(format, flavor, |buf, marker| {
+            match marker {
+                Marker::Value(_, arg) => {
+                    values.push(arg.clone());
+                    buf.push_str(&flavor.placeholder(values.len()));
+                }
+                Marker::Builder(_, builder) => {
+                    let (sql, args) = builder.build_with_flavor(flavor.clone(), std::mem::take(&mut values))?;
+                    values = args;
+                    buf.push_str(&sql);
+                }
+            }
+
+            Ok(())
+        })?;
+
+        Ok((sql, values))
+    }
+
+    // 扫描 format 中的占位标记，每遇到一个参数值或子查询就调用一次 write
+    // 表达式先按 flavor 渲染，再递归展开其中的占位标记
+    pub(crate) fn compile_markers<F>(&self, format: &str, flavor: &Flavor, mut write: F) -> Result<String, Error>
+    where
+        F: FnMut(&mut String, Marker) -> Result<(), Error>,
+    {
+        let mut buf = String::with_capacity(format.len());
+        self.write_markers(&mut buf, format, flavor, &mut write)?;
+        Ok(buf)
+    }
+
+    fn write_markers(
+        &self,
+        buf: &mut String,
+        format: &str,
+        flavor: &Flavor,
+        write: &mut dyn FnMut(&mut String, Marker) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        let mut chars = format.char_indices().peekable();
+
+        while let Some((pos, c)) = chars.next() {
+            if c != '$' {
+                buf.push(c);
+                continue;
+            }
+
+            if let Some(&(open, '{')) = chars.peek() {
+                let named = format[open + 1..]
+                    .split_once('}')
+                    .and_then(|(name, _)| self.named_args.get(name).map(|&idx| (name, idx)));
+
+                if let Some((name, idx)) = named {
+                    let end = open + name.len() + 1;
+
+                    while chars.next_if(|&(i, _)| i <= end).is_some() {}
+
+                    self.write_arg(buf, Some(name), idx, flavor, write)?;
+                } else {
+                    buf.push('$');
+                }
+
+                continue;
+            }
+
+            let digits_len = format[pos + 1..]
+                .bytes()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+
+            if digits_len == 0 {
+                chars.next_if(|&(_, c)| c == '$');
+                buf.push('$');
+                continue;
+            }
+
+            let digits = &format[pos + 1..pos + 1 + digits_len];
+
+            while chars.next_if(|&(_, c)| c.is_ascii_digit()).is_some() {}
+
+            let idx = digits.parse::<usize>().unwrap_or(usize::MAX);
+
+            if idx >= self.args.len() {
+                return Err(Error::MissingArgs {
+                    expected: idx.saturating_add(1),
+                    got: self.args.len(),
+                    at: pos,
+                });
+            }
+
+            self.write_arg(buf, None, idx, flavor, write)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_arg(
+        &self,
+        buf: &mut String,
+        name: Option<&str>,
+        idx: usize,
+        flavor: &Flavor,
+        write: &mut dyn FnMut(&mut String, Marker) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        match &self.args[idx] {
+            Arg::Value(value) => write(buf, Marker::Value(name, value)),
+            Arg::Builder(builder) => write(buf, Marker::Builder(idx, builder.as_ref())),
+            Arg::Expr(expr) => {
+                let mut nested = Args {
+                    flavor: flavor.clone(),
+                    mode: self.mode,
+                    pack_in_lists: self.pack_in_lists,
+                    args: Vec::new(),
+                    named_args: HashMap::new(),
+                };
+                let format = expr.render(&mut nested)?;
+                nested.write_markers(buf, &format, flavor, write)
+            }
+        }
+    }
+}
+
+// 转义字符串
+// $ 在编译时有特殊含义，需要写成 $$
+pub fn escape(s: &str) -> String {
+    s.replace('$', "$$")
+}
+
+impl Default for Args {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::RawBuilder;
+
+    #[test]
+    fn test_compile() {
+        let mut args = Args::new();
+        let id = args.add(1);
+        let name = args.add_named("name", "a");
+        args.add_named("name", "b");
+        let format = format!("SELECT '$$' FROM t WHERE id = {} AND name = {} OR alias = {} AND ${{other}}", id, name, name);
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::PostgreSQL, vec![Value::Null]).unwrap();
+        assert_eq!(sql, "SELECT '$' FROM t WHERE id = $2 AND name = $3 OR alias = $4 AND ${other}");
+        assert_eq!(
+            values,
+            vec![
+                Value::Null,
+                Value::Int(1),
+                Value::String("b".to_string()),
+                Value::String("b".to_string()),
+            ]
+        );
+
+        let err = args.compile_with_flavor("SELECT $0, $2", Flavor::MySQL, Vec::new()).unwrap_err();
+        assert!(matches!(err, Error::MissingArgs { expected: 3, got: 2, at: 11 }));
+    }
+
+    #[test]
+    fn test_compile_nested() {
+        let mut args = Args::new();
+        let subquery = Expr::subquery(RawBuilder::new(|args| format!("SELECT id FROM u WHERE a = {}", args.add(2))));
+        let format = format!("SELECT * FROM t WHERE b = {} AND id IN {}", args.add(1), args.add_expr(subquery));
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::SQLServer, Vec::new()).unwrap();
+        assert_eq!(sql, "SELECT * FROM t WHERE b = @p1 AND id IN (SELECT id FROM u WHERE a = @p2)");
+        assert_eq!(values, vec![Value::Int(1), Value::Int(2)]);
+
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::Oracle, Vec::new()).unwrap();
+        assert_eq!(sql, "SELECT * FROM t WHERE b = :1 AND id IN (SELECT id FROM u WHERE a = :2)");
+    }
+}
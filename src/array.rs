@@ -0,0 +1,182 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::dialect::Feature;
+use crate::error::Error;
+use crate::expr::{BinaryOp, Expr};
+use crate::flavor::Flavor;
+use crate::func::{call, Function};
+use crate::value::{ToSqlValue, Value};
+
+// IN 列表中的值不少于这个数量时，PostgreSQL 改写为 = ANY($1)，整个列表绑定为一个数组参数
+// 长度不同的列表生成相同的 SQL，预编译语句的缓存不会随列表长度增长
+static ANY_THRESHOLD: AtomicUsize = AtomicUsize::new(32);
+
+// 设置改写为 = ANY($1) 的最小列表长度，返回原来的值，默认为 32，设置为 usize::MAX 时不再改写
+pub fn set_any_threshold(threshold: usize) -> usize {
+    ANY_THRESHOLD.swap(threshold, Ordering::Relaxed)
+}
+
+// 返回改写为 = ANY($1) 的最小列表长度
+pub fn any_threshold() -> usize {
+    ANY_THRESHOLD.load(Ordering::Relaxed)
+}
+
+// 返回数组 array 包含元素 element 的条件：element = ANY(array) 或 has(array, element)
+pub fn array_contains(array: impl Into<Expr>, element: impl Into<Expr>) -> Expr {
+    call(Function::ArrayContains, vec![array.into(), element.into()])
+}
+
+// 返回两个数组至少有一个相同元素的条件：array && other 或 hasAny(array, other)
+// other 通常是 Value::Array，在 ClickHouse 中展开为 array(?, ?, ...)
+pub fn array_overlap(array: impl Into<Expr>, other: impl Into<Expr>) -> Expr {
+    call(Function::ArrayOverlap, vec![array.into(), other.into()])
+}
+
+// 返回 expr 等于 values 中任意一个的条件
+// PostgreSQL 渲染为 expr = ANY($1)，values 绑定为一个数组参数；其他方言渲染为 expr IN (...)
+pub fn equal_any<T: ToSqlValue>(expr: impl Into<Expr>, values: impl IntoIterator<Item = T>) -> Expr {
+    call(Function::EqualAny, vec![expr.into(), Expr::Value(Value::array(values))])
+}
+
+fn unsupported(flavor: &Flavor) -> Error {
+    Error::UnsupportedFeature {
+        flavor: flavor.clone(),
+        feature: Feature::Array,
+    }
+}
+
+// ClickHouse 的驱动不能绑定数组，数组参数展开为 array(?, ?, ...)
+fn clickhouse_array(expr: &Expr) -> Expr {
+    match expr {
+        Expr::Value(Value::Array(values)) => Expr::func("array", values.iter().cloned().map(Expr::Value)),
+        _ => expr.clone(),
+    }
+}
+
+fn is_empty_array(expr: &Expr) -> bool {
+    matches!(expr, Expr::Value(Value::Array(values)) if values.is_empty())
+}
+
+pub(crate) fn lower_contains(flavor: &Flavor, array: &Expr, element: &Expr) -> Result<Expr, Error> {
+    let expr = match flavor {
+        Flavor::PostgreSQL => element.clone().eq(Expr::func("ANY", [array.clone()])),
+        Flavor::ClickHouse => Expr::func("has", [clickhouse_array(array), element.clone()]),
+        _ => return Err(unsupported(flavor)),
+    };
+
+    Ok(expr)
+}
+
+pub(crate) fn lower_overlap(flavor: &Flavor, array: &Expr, other: &Expr) -> Result<Expr, Error> {
+    let expr = match flavor {
+        // 空数组没有元素类型，直接返回恒假的条件
+        Flavor::PostgreSQL | Flavor::ClickHouse if is_empty_array(array) || is_empty_array(other) => Expr::raw("0 = 1"),
+        Flavor::PostgreSQL => array.clone().binary(BinaryOp::Other("&&"), other.clone()),
+        Flavor::ClickHouse => Expr::func("hasAny", [clickhouse_array(array), clickhouse_array(other)]),
+        _ => return Err(unsupported(flavor)),
+    };
+
+    Ok(expr)
+}
+
+pub(crate) fn lower_equal_any(flavor: &Flavor, expr: &Expr, values: &Expr) -> Result<Expr, Error> {
+    match (flavor, values) {
+        (_, Expr::Value(Value::Array(values))) if values.is_empty() => Ok(Expr::raw("0 = 1")),
+        (Flavor::PostgreSQL, _) => Ok(expr.clone().eq(Expr::func("ANY", [values.clone()]))),
+        (_, Expr::Value(Value::Array(values))) => Ok(expr.clone().in_(values.iter().cloned().map(Expr::Value))),
+        _ => lower_contains(flavor, values, expr),
+    }
+}
+
+// PostgreSQL 中将较长的 IN 列表改写为 = ANY($1)，NOT IN 改写为 <> ALL($1)
+// 只改写全部由同一类型的参数值组成的列表，NULL 除外；数组参数只有一种元素类型，
+// 包含列、子查询、数组或不同类型的值时保持原样
+pub(crate) fn rewrite_in_list(flavor: &Flavor, op: BinaryOp, left: &Expr, values: &[Expr]) -> Option<Expr> {
+    if !matches!(flavor, Flavor::PostgreSQL) || values.len() < any_threshold() {
+        return None;
+    }
+
+    let values = values
+        .iter()
+        .map(|v| match v {
+            Expr::Value(Value::Array(_)) => None,
+            Expr::Value(v) => Some(v.clone()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let mut kinds = values.iter().filter(|v| !v.is_null()).map(Value::kind);
+    let kind = kinds.next()?;
+
+    if kinds.any(|k| k != kind) {
+        return None;
+    }
+
+    let array = Expr::Value(Value::Array(values));
+    let expr = match op {
+        BinaryOp::In => left.clone().eq(Expr::func("ANY", [array])),
+        _ => left.clone().ne(Expr::func("ALL", [array])),
+    };
+
+    Some(expr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::Args;
+    use crate::cond::Cond;
+
+    #[test]
+    fn test_array() {
+        let mut args = Args::new();
+        let mut cond = Cond::new(&mut args);
+        let contains = cond.array_contains("tags", "rust");
+        let overlap = cond.array_overlap("tags", ["go", "zig"]);
+        let any = cond.equal_any("id", [1, 2, 3]);
+        let format = cond.and(&[contains, overlap, any]);
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(sql, "($1 = ANY(tags) AND tags && $2 AND id = ANY($3))");
+        assert_eq!(values[1], Value::array(["go", "zig"]));
+        assert_eq!(values[2], Value::array([1, 2, 3]));
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::ClickHouse, Vec::new()).unwrap();
+        assert_eq!(sql, "(has(tags, ?) AND hasAny(tags, array(?, ?)) AND id IN (?, ?, ?))");
+        assert_eq!(values.len(), 6);
+
+        let err = args.compile_with_flavor(&format, Flavor::MySQL, Vec::new()).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedFeature { feature: Feature::Array, .. }));
+
+        let mut args = Args::new();
+        let mut cond = Cond::new(&mut args);
+        let small = cond.in_("id", 0..3);
+        let large = cond.not_in("id", 0..any_threshold() as i32);
+        let empty = cond.equal_any("id", Vec::<i32>::new());
+        let format = cond.and(&[small, large, empty]);
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(sql, "(id IN ($1, $2, $3) AND id <> ALL($4) AND 0 = 1)");
+        assert_eq!(values.len(), 4);
+        assert_eq!(values[3], Value::array(0..any_threshold() as i32));
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::MySQL, Vec::new()).unwrap();
+        assert!(sql.starts_with("(id IN (?, ?, ?) AND id NOT IN (?, ?"));
+        assert_eq!(values.len(), 3 + any_threshold());
+
+        // 不同类型的值不能绑定为一个数组
+        let mut args = Args::new();
+        let mixed: Vec<Expr> = (0..any_threshold() as i64).map(Expr::val).chain([Expr::val("x")]).collect();
+        let format = args.add_expr(Expr::col("id").in_(mixed));
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert!(sql.starts_with("id IN ($1, $2"));
+        assert_eq!(values.len(), any_threshold() + 1);
+
+        // NULL 可以作为数组的元素
+        let mut args = Args::new();
+        let values: Vec<Option<i64>> = (0..any_threshold() as i64).map(Some).chain([None]).collect();
+        let format = args.add_expr(Expr::col("id").in_(values));
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(sql, "id = ANY($1)");
+    }
+}
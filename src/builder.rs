@@ -0,0 +1,55 @@
+use crate::error::Error;
+use crate::flavor::Flavor;
+use crate::format::format_sql;
+use crate::interpolate::debug_sql;
+use crate::value::Value;
+
+// 构造器遇到 Flavor 不支持的特性时的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BuildMode {
+    // 尽量用等价的写法模拟，无法模拟时原样生成
+    #[default]
+    Lenient,
+    // 返回 UnsupportedFeature 错误
+    Strict,
+}
+
+// Builder 是所有 SQL 构造器的公共接口
+// 数据库驱动的集成都基于这个接口实现，对所有构造器生效
+pub trait Builder {
+    // 使用指定的 Flavor 编译 SQL 字符串和参数
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: Vec<Value>) -> Result<(String, Vec<Value>), Error>;
+
+    // 返回构造器当前的 Flavor
+    fn flavor(&self) -> Flavor;
+
+    // 使用当前的 Flavor 编译 SQL 字符串和参数
+    // 错误包装为 Error::Build，带有构造器的类型名，用 Error::root 取得具体的错误
+    fn build(&self) -> Result<(String, Vec<Value>), Error> {
+        self.build_with_flavor(self.flavor(), Vec::new())
+            .map_err(|err| Error::Build {
+                builder: builder_name::<Self>(),
+                source: Box::new(err),
+            })
+    }
+
+    // 返回格式化为多行的 SQL 字符串，便于在日志和测试中阅读
+    fn pretty(&self) -> Result<String, Error> {
+        let (sql, _) = self.build()?;
+        Ok(format_sql(&sql, &self.flavor()))
+    }
+
+    // 返回内联了参数值的 SQL 字符串，用于调试和日志
+    // 无法安全内联的参数会以 /* ?=value */ 注释的形式标注在占位符之后
+    fn to_debug_string(&self) -> Result<String, Error> {
+        let (sql, args) = self.build()?;
+        Ok(debug_sql(&self.flavor(), &sql, &args))
+    }
+}
+
+// 返回去掉模块路径和泛型参数的类型名，例如 CreateTableBuilder
+fn builder_name<B: ?Sized>() -> &'static str {
+    let name = std::any::type_name::<B>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
@@ -0,0 +1,123 @@
+use crate::expr::Expr;
+
+// CaseBuilder 用于构造 CASE WHEN 表达式
+// 分支中的值不会立即绑定，转换为 Expr 后通过 Args::add_expr 添加到外层语句的 Args 中，
+// 因此可以用在查询的列、UPDATE 的 SET 和 ORDER BY 等任何接受字符串的地方
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CaseBuilder {
+    operand: Option<Expr>,
+    whens: Vec<(Expr, Expr)>,
+    otherwise: Option<Expr>,
+}
+
+impl CaseBuilder {
+    // 创建 CASE WHEN condition THEN value ... END 形式的构造器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 创建 CASE operand WHEN value THEN result ... END 形式的构造器
+    pub fn on(operand: impl Into<Expr>) -> Self {
+        Self {
+            operand: Some(operand.into()),
+            ..Self::default()
+        }
+    }
+
+    // 添加 WHEN condition THEN value 分支
+    // 简单 CASE 中 condition 是与 operand 比较的值
+    pub fn when(mut self, condition: impl Into<Expr>, value: impl Into<Expr>) -> Self {
+        self.whens.push((condition.into(), value.into()));
+        self
+    }
+
+    // 设置 ELSE 分支
+    pub fn else_(mut self, value: impl Into<Expr>) -> Self {
+        self.otherwise = Some(value.into());
+        self
+    }
+
+    // 返回分支的数量
+    pub fn num_when(&self) -> usize {
+        self.whens.len()
+    }
+
+    // 返回构造好的表达式，没有分支时为 ELSE 的值或 NULL
+    pub fn end(self) -> Expr {
+        Expr::Case {
+            operand: self.operand.map(Box::new),
+            whens: self.whens,
+            otherwise: self.otherwise.map(Box::new),
+        }
+    }
+}
+
+// 返回 CASE WHEN condition THEN value ... END 形式的构造器
+pub fn case() -> CaseBuilder {
+    CaseBuilder::new()
+}
+
+// 返回 CASE operand WHEN value THEN result ... END 形式的构造器
+pub fn case_on(operand: impl Into<Expr>) -> CaseBuilder {
+    CaseBuilder::on(operand)
+}
+
+impl From<CaseBuilder> for Expr {
+    fn from(builder: CaseBuilder) -> Self {
+        builder.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::Args;
+    use crate::builder::Builder;
+    use crate::flavor::Flavor;
+    use crate::value::Value;
+
+    #[test]
+    fn test_case_builder() {
+        let tier = case()
+            .when(Expr::col("total").ge(1000), "gold")
+            .when(Expr::col("total").ge(100), "silver")
+            .else_("bronze");
+        let priority = case_on(Expr::col("status")).when("open", 1).when("pending", 2).else_(3);
+
+        let mut args = Args::new();
+        let format = format!(
+            "UPDATE users SET tier = {} WHERE id = {} ORDER BY {} DESC",
+            args.add_expr(tier),
+            args.add(7),
+            args.add_expr(priority)
+        );
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            "UPDATE users SET tier = CASE WHEN total >= $1 THEN $2 WHEN total >= $3 THEN $4 ELSE $5 END \
+             WHERE id = $6 ORDER BY CASE status WHEN $7 THEN $8 WHEN $9 THEN $10 ELSE $11 END DESC"
+        );
+        assert_eq!(values[1], Value::String("gold".to_string()));
+        assert_eq!(values[5], Value::Int(7));
+        assert_eq!(values.len(), 11);
+
+        let mut args = Args::new();
+        let format = args.add_expr(CaseBuilder::new().else_(Expr::col("a") + 1));
+        assert_eq!(args.compile_with_flavor(&format, Flavor::MySQL, Vec::new()).unwrap().0, "(a + ?)");
+    }
+
+    #[test]
+    fn test_case_in_builder() {
+        let total = 1200;
+        let tier = case().when(Expr::val(total).ge(1000), "gold").else_("bronze");
+        let mut ib = Flavor::PostgreSQL.new_insert_builder();
+        ib.set_table("users");
+        ib.cols(&["name", "tier"]).values([Expr::val("a"), tier.end()]);
+
+        let (sql, values) = ib.build().unwrap();
+        assert_eq!(sql, "INSERT INTO users (name, tier) VALUES ($1, CASE WHEN $2 >= $3 THEN $4 ELSE $5 END)");
+        assert_eq!(values[1], Value::Int(1200));
+        assert_eq!(values[3], Value::String("gold".to_string()));
+    }
+}
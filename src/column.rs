@@ -0,0 +1,288 @@
+use std::marker::PhantomData;
+
+use crate::cond::Condition;
+use crate::value::{ToSqlValue, Value};
+
+// Column 是带类型的列，T 为列中值的 Rust 类型
+// 比较方法只接受能转换为 T 的值，在编译期检查值与列的类型是否匹配
+// 通常由 table! 宏生成，生成的 Condition 使用带表名的列名，通过 Cond::condition 写入构造器的 Args
+pub struct Column<T> {
+    table: &'static str,
+    name: &'static str,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Column<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Column<T> {}
+
+impl<T> std::fmt::Debug for Column<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Column({}.{})", self.table, self.name)
+    }
+}
+
+impl<T> Column<T> {
+    // 创建一个新的 Column 实例
+    pub const fn new(table: &'static str, name: &'static str) -> Self {
+        Self {
+            table,
+            name,
+            _marker: PhantomData,
+        }
+    }
+
+    // 返回表名
+    pub fn table(&self) -> &'static str {
+        self.table
+    }
+
+    // 返回列名
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    // 返回带表名的列名，例如 users.id
+    pub fn qualified(&self) -> String {
+        format!("{}.{}", self.table, self.name)
+    }
+
+    // 返回 column IS NULL
+    pub fn is_null(&self) -> Condition {
+        Condition::IsNull {
+            field: self.qualified(),
+            negated: false,
+        }
+    }
+
+    // 返回 column IS NOT NULL
+    pub fn is_not_null(&self) -> Condition {
+        Condition::IsNull {
+            field: self.qualified(),
+            negated: true,
+        }
+    }
+}
+
+impl<T: ToSqlValue> Column<T> {
+    // 返回 column = value
+    pub fn eq(&self, value: impl Into<T>) -> Condition {
+        self.compare("=", value.into().to_sql_value())
+    }
+
+    // 返回 column <> value
+    pub fn ne(&self, value: impl Into<T>) -> Condition {
+        self.compare("<>", value.into().to_sql_value())
+    }
+
+    // 返回 column > value
+    pub fn gt(&self, value: impl Into<T>) -> Condition {
+        self.compare(">", value.into().to_sql_value())
+    }
+
+    // 返回 column >= value
+    pub fn ge(&self, value: impl Into<T>) -> Condition {
+        self.compare(">=", value.into().to_sql_value())
+    }
+
+    // 返回 column < value
+    pub fn lt(&self, value: impl Into<T>) -> Condition {
+        self.compare("<", value.into().to_sql_value())
+    }
+
+    // 返回 column <= value
+    pub fn le(&self, value: impl Into<T>) -> Condition {
+        self.compare("<=", value.into().to_sql_value())
+    }
+
+    // 返回 column IN (values)
+    pub fn in_<V: Into<T>>(&self, values: impl IntoIterator<Item = V>) -> Condition {
+        self.in_list(values, false)
+    }
+
+    // 返回 column NOT IN (values)
+    pub fn not_in<V: Into<T>>(&self, values: impl IntoIterator<Item = V>) -> Condition {
+        self.in_list(values, true)
+    }
+
+    // 返回 column BETWEEN lower AND upper
+    pub fn between(&self, lower: impl Into<T>, upper: impl Into<T>) -> Condition {
+        Condition::Between {
+            field: self.qualified(),
+            lower: lower.into().to_sql_value(),
+            upper: upper.into().to_sql_value(),
+            negated: false,
+        }
+    }
+
+    // 返回 column NOT BETWEEN lower AND upper
+    pub fn not_between(&self, lower: impl Into<T>, upper: impl Into<T>) -> Condition {
+        Condition::Between {
+            field: self.qualified(),
+            lower: lower.into().to_sql_value(),
+            upper: upper.into().to_sql_value(),
+            negated: true,
+        }
+    }
+
+    // 与 NULL 比较的结果总是 NULL，= NULL 和 <> NULL 改写为 IS NULL 和 IS NOT NULL
+    fn compare(&self, op: &'static str, value: Value) -> Condition {
+        match (op, &value) {
+            ("=", Value::Null) => self.is_null(),
+            ("<>", Value::Null) => self.is_not_null(),
+            _ => Condition::Compare {
+                field: self.qualified(),
+                op,
+                value,
+            },
+        }
+    }
+
+    fn in_list<V: Into<T>>(&self, values: impl IntoIterator<Item = V>, negated: bool) -> Condition {
+        Condition::In {
+            field: self.qualified(),
+            values: values.into_iter().map(|v| v.into().to_sql_value()).collect(),
+            negated,
+        }
+    }
+}
+
+// 文本类型的列，支持 LIKE
+pub trait TextColumn: ToSqlValue {}
+
+impl TextColumn for String {}
+
+impl TextColumn for Option<String> {}
+
+impl<T: TextColumn> Column<T> {
+    // 返回 column LIKE pattern
+    pub fn like(&self, pattern: &str) -> Condition {
+        self.compare("LIKE", pattern.to_sql_value())
+    }
+
+    // 返回 column NOT LIKE pattern
+    pub fn not_like(&self, pattern: &str) -> Condition {
+        self.compare("NOT LIKE", pattern.to_sql_value())
+    }
+}
+
+// 声明表和带类型的列
+// 每张表生成一个同名模块，包含表名 TABLE、列名列表 COLUMNS 和每一列的 Column 常量：
+//
+//     table! {
+//         pub users {
+//             id: i64,
+//             email: String,
+//             deleted_at: Option<String>,
+//         }
+//     }
+//
+//     let cond = users::id.eq(5).and(users::email.like("%@x"));
+//
+// 列的类型在生成的模块中解析，自定义类型需要写成 super::Type 或完整路径
+#[macro_export]
+macro_rules! table {
+    ($($(#[$meta:meta])* $vis:vis $table:ident { $($column:ident : $ty:ty),* $(,)? })*) => {
+        $(
+            $(#[$meta])*
+            // 生成的常量不一定都会被用到
+            #[allow(non_upper_case_globals, dead_code)]
+            $vis mod $table {
+                // 表名
+                pub const TABLE: &str = stringify!($table);
+
+                // 所有列名，按声明的顺序排列
+                pub const COLUMNS: &[&str] = &[$(stringify!($column)),*];
+
+                $(
+                    pub const $column: $crate::column::Column<$ty> =
+                        $crate::column::Column::new(stringify!($table), stringify!($column));
+                )*
+            }
+        )*
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::args::Args;
+    use crate::cond::Cond;
+    use crate::flavor::Flavor;
+    use crate::value::Value;
+
+    table! {
+        users {
+            id: i64,
+            email: String,
+            deleted_at: Option<String>,
+        }
+
+        orders {
+            id: u32,
+            user_id: i64,
+        }
+
+        items {
+            order: i32,
+            Name: String,
+        }
+    }
+
+    #[test]
+    fn test_table() {
+        assert_eq!(users::TABLE, "users");
+        assert_eq!(users::COLUMNS, &["id", "email", "deleted_at"]);
+        assert_eq!(users::email.name(), "email");
+        assert_eq!(orders::user_id.qualified(), "orders.user_id");
+
+        let condition = users::id
+            .eq(5)
+            .and(users::email.like("%@x"))
+            .and(users::deleted_at.is_null())
+            .and(orders::id.in_([1u32, 2]).or(orders::user_id.between(10, 20)));
+
+        let mut args = Args::new();
+        let format = Cond::new(&mut args).condition(&condition);
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            "(users.id = $1 AND users.email LIKE $2 AND users.deleted_at IS NULL AND (orders.id IN ($3, $4) OR orders.user_id BETWEEN $5 AND $6))"
+        );
+        assert_eq!(
+            values,
+            vec![
+                Value::BigInt(5),
+                Value::String("%@x".to_string()),
+                Value::Unsigned(1),
+                Value::Unsigned(2),
+                Value::BigInt(10),
+                Value::BigInt(20),
+            ]
+        );
+
+        // 与 None 比较改写为 IS NULL 和 IS NOT NULL
+        let condition = users::deleted_at.eq(None).or(users::deleted_at.ne(None));
+        let mut args = Args::new();
+        let format = Cond::new(&mut args).condition(&condition);
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::MySQL, Vec::new()).unwrap();
+        assert_eq!(sql, "(users.deleted_at IS NULL OR users.deleted_at IS NOT NULL)");
+        assert!(values.is_empty());
+
+        // 保留字和大小写混合的列名与 Expr::Column 一样加引号
+        let condition = items::order.gt(1).and(items::Name.in_(["a"])).and(items::order.between(1, 2));
+        let mut args = Args::new();
+        let format = Cond::new(&mut args).condition(&condition);
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            "(items.\"order\" > $1 AND items.\"Name\" IN ($2) AND items.\"order\" BETWEEN $3 AND $4)"
+        );
+
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::MySQL, Vec::new()).unwrap();
+        assert_eq!(sql, "(items.`order` > ? AND items.Name IN (?) AND items.`order` BETWEEN ? AND ?)");
+    }
+}
@@ -0,0 +1,295 @@
+use std::ops::Not;
+
+use crate::args::{escape, Args};
+use crate::array;
+use crate::expr::Expr;
+use crate::json;
+use crate::search::{self, SearchMode};
+use crate::value::{ToSqlValue, Value};
+
+// Cond 用于构造 WHERE、HAVING 等子句中的条件表达式
+// 条件中的参数添加到 Args 中，返回的字符串包含占位标记，由构造器统一编译
+// 字段名中的 $ 会被转义
+pub struct Cond<'a> {
+    args: &'a mut Args,
+}
+
+impl<'a> Cond<'a> {
+    // 创建一个新的 Cond 实例，参数添加到 args 中
+    pub fn new(args: &'a mut Args) -> Self {
+        Self { args }
+    }
+
+    // 添加参数并返回占位标记
+    pub fn var(&mut self, value: impl ToSqlValue) -> String {
+        self.args.add(value)
+    }
+
+    // 添加表达式并返回占位标记，表达式在编译时按目标 Flavor 渲染
+    pub fn expr(&mut self, expr: impl Into<Expr>) -> String {
+        self.args.add_expr(expr)
+    }
+
+    // 返回 field = value
+    pub fn equal(&mut self, field: &str, value: impl ToSqlValue) -> String {
+        self.compare(field, "=", value.to_sql_value())
+    }
+
+    // 返回 field <> value
+    pub fn not_equal(&mut self, field: &str, value: impl ToSqlValue) -> String {
+        self.compare(field, "<>", value.to_sql_value())
+    }
+
+    // 返回 field > value
+    pub fn greater_than(&mut self, field: &str, value: impl ToSqlValue) -> String {
+        self.compare(field, ">", value.to_sql_value())
+    }
+
+    // 返回 field >= value
+    pub fn greater_equal_than(&mut self, field: &str, value: impl ToSqlValue) -> String {
+        self.compare(field, ">=", value.to_sql_value())
+    }
+
+    // 返回 field < value
+    pub fn less_than(&mut self, field: &str, value: impl ToSqlValue) -> String {
+        self.compare(field, "<", value.to_sql_value())
+    }
+
+    // 返回 field <= value
+    pub fn less_equal_than(&mut self, field: &str, value: impl ToSqlValue) -> String {
+        self.compare(field, "<=", value.to_sql_value())
+    }
+
+    // 返回 field LIKE value
+    pub fn like(&mut self, field: &str, value: impl ToSqlValue) -> String {
+        self.compare(field, "LIKE", value.to_sql_value())
+    }
+
+    // 返回 field NOT LIKE value
+    pub fn not_like(&mut self, field: &str, value: impl ToSqlValue) -> String {
+        self.compare(field, "NOT LIKE", value.to_sql_value())
+    }
+
+    // 返回 field IS NULL
+    pub fn is_null(&mut self, field: &str) -> String {
+        format!("{} IS NULL", escape(field))
+    }
+
+    // 返回 field IS NOT NULL
+    pub fn is_not_null(&mut self, field: &str) -> String {
+        format!("{} IS NOT NULL", escape(field))
+    }
+
+    // 返回 field 中的 JSON 文档包含 candidate 的条件，candidate 是 JSON 文本
+    pub fn json_contains(&mut self, field: &str, candidate: impl ToSqlValue) -> String {
+        self.args.add_expr(json::json_contains(Expr::raw(field), Expr::Value(candidate.to_sql_value())))
+    }
+
+    // 返回 field 中的 JSON 对象包含顶层的键 key 的条件
+    pub fn json_has_key(&mut self, field: &str, key: &str) -> String {
+        self.args.add_expr(json::json_has_key(Expr::raw(field), key))
+    }
+
+    // 返回 fields 匹配 query 的全文检索条件，query 绑定为参数
+    pub fn match_against(&mut self, fields: &[&str], query: impl ToSqlValue, mode: SearchMode) -> String {
+        let columns = fields.iter().map(|field| Expr::raw(field));
+        self.args.add_expr(search::match_against(columns, Expr::Value(query.to_sql_value()), mode))
+    }
+
+    // 返回数组类型的 field 包含 value 的条件
+    pub fn array_contains(&mut self, field: &str, value: impl ToSqlValue) -> String {
+        self.args.add_expr(array::array_contains(Expr::raw(field), Expr::Value(value.to_sql_value())))
+    }
+
+    // 返回数组类型的 field 与 values 至少有一个相同元素的条件
+    pub fn array_overlap<T: ToSqlValue>(&mut self, field: &str, values: impl IntoIterator<Item = T>) -> String {
+        self.args.add_expr(array::array_overlap(Expr::raw(field), Expr::Value(Value::array(values))))
+    }
+
+    // 返回 field 等于 values 中任意一个的条件，PostgreSQL 中 values 绑定为一个数组参数
+    pub fn equal_any<T: ToSqlValue>(&mut self, field: &str, values: impl IntoIterator<Item = T>) -> String {
+        self.args.add_expr(array::equal_any(Expr::raw(field), values))
+    }
+
+    // 返回 field IN (values)，values 为空时返回恒假的 0 = 1
+    // 值较多时按方言改写为 field = ANY($1)、表函数子查询或多个 IN 列表，见 in_list::rewrite
+    pub fn in_<T: ToSqlValue>(&mut self, field: &str, values: impl IntoIterator<Item = T>) -> String {
+        self.args.add_expr(Expr::raw(field).in_(values.into_iter().map(|v| Expr::Value(v.to_sql_value()))))
+    }
+
+    // 返回 field NOT IN (values)，values 为空时返回恒真的 0 = 0
+    // 值较多时按方言改写为 field <> ALL($1)、表函数子查询或多个 NOT IN 列表，见 in_list::rewrite
+    pub fn not_in<T: ToSqlValue>(&mut self, field: &str, values: impl IntoIterator<Item = T>) -> String {
+        self.args.add_expr(Expr::raw(field).not_in(values.into_iter().map(|v| Expr::Value(v.to_sql_value()))))
+    }
+
+    // 返回 field BETWEEN lower AND upper
+    pub fn between(&mut self, field: &str, lower: impl ToSqlValue, upper: impl ToSqlValue) -> String {
+        format!("{} BETWEEN {} AND {}", escape(field), self.var(lower), self.var(upper))
+    }
+
+    // 返回 field NOT BETWEEN lower AND upper
+    pub fn not_between(&mut self, field: &str, lower: impl ToSqlValue, upper: impl ToSqlValue) -> String {
+        format!("{} NOT BETWEEN {} AND {}", escape(field), self.var(lower), self.var(upper))
+    }
+
+    // 用 OR 连接多个表达式，忽略空表达式
+    pub fn or(&mut self, exprs: &[String]) -> String {
+        join(exprs, " OR ")
+    }
+
+    // 用 AND 连接多个表达式，忽略空表达式
+    pub fn and(&mut self, exprs: &[String]) -> String {
+        join(exprs, " AND ")
+    }
+
+    // 返回 NOT expr
+    pub fn not(&mut self, expr: &str) -> String {
+        if expr.is_empty() {
+            return String::new();
+        }
+
+        format!("NOT {}", expr)
+    }
+
+    // 将 Condition 写入 Args 并返回表达式
+    // Condition 的字段是列名，与 Expr::Column 一样在编译时按 Flavor 加引号
+    pub fn condition(&mut self, condition: &Condition) -> String {
+        match condition {
+            Condition::And(conditions) => {
+                let exprs: Vec<String> = conditions.iter().map(|c| self.condition(c)).collect();
+                self.and(&exprs)
+            }
+            Condition::Or(conditions) => {
+                let exprs: Vec<String> = conditions.iter().map(|c| self.condition(c)).collect();
+                self.or(&exprs)
+            }
+            Condition::Not(condition) => {
+                let expr = self.condition(condition);
+                self.not(&expr)
+            }
+            leaf => self.args.add_expr(leaf.clone()),
+        }
+    }
+
+    fn compare(&mut self, field: &str, op: &str, value: Value) -> String {
+        format!("{} {} {}", escape(field), op, self.var(value))
+    }
+}
+
+// 用分隔符连接非空表达式，多于一个时加括号
+fn join(exprs: &[String], sep: &str) -> String {
+    let exprs: Vec<&str> = exprs.iter().map(String::as_str).filter(|e| !e.is_empty()).collect();
+
+    match exprs.len() {
+        0 => String::new(),
+        1 => exprs[0].to_string(),
+        _ => format!("({})", exprs.join(sep)),
+    }
+}
+
+// Condition 是尚未添加到 Args 的条件，通常由 Column 的方法生成
+// 通过 Cond::condition 写入构造器的 Args
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    // field op value，例如 id = 1、name LIKE 'a%'
+    Compare { field: String, op: &'static str, value: Value },
+    // field IS NULL 或 field IS NOT NULL
+    IsNull { field: String, negated: bool },
+    // field IN (...) 或 field NOT IN (...)
+    In { field: String, values: Vec<Value>, negated: bool },
+    // field BETWEEN lower AND upper 或 field NOT BETWEEN lower AND upper
+    Between { field: String, lower: Value, upper: Value, negated: bool },
+    And(Vec<Condition>),
+    Or(Vec<Condition>),
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    // 返回 self AND other
+    pub fn and(self, other: Condition) -> Condition {
+        match self {
+            Condition::And(mut conditions) => {
+                conditions.push(other);
+                Condition::And(conditions)
+            }
+            _ => Condition::And(vec![self, other]),
+        }
+    }
+
+    // 返回 self OR other
+    pub fn or(self, other: Condition) -> Condition {
+        match self {
+            Condition::Or(mut conditions) => {
+                conditions.push(other);
+                Condition::Or(conditions)
+            }
+            _ => Condition::Or(vec![self, other]),
+        }
+    }
+}
+
+// 实现 ! 运算符，返回 NOT self
+impl Not for Condition {
+    type Output = Condition;
+
+    fn not(self) -> Condition {
+        Condition::Not(Box::new(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flavor::Flavor;
+
+    #[test]
+    fn test_cond() {
+        let mut args = Args::new();
+        let mut cond = Cond::new(&mut args);
+        let exprs = vec![
+            cond.equal("id", 1),
+            cond.like("name", "a%"),
+            cond.in_("status", [1, 2]),
+            cond.not_in("tag", Vec::<i32>::new()),
+            cond.between("age", 18, 30),
+            cond.is_null("deleted_at"),
+        ];
+        let ne = cond.not_equal("id", 2);
+        let or = cond.or(&[ne, String::new()]);
+        let and = cond.and(&exprs);
+        let format = cond.and(&[and, or]);
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            "((id = $1 AND name LIKE $2 AND status IN ($3, $4) AND 0 = 0 AND age BETWEEN $5 AND $6 AND deleted_at IS NULL) AND id <> $7)"
+        );
+        assert_eq!(values.len(), 7);
+        assert_eq!(values[1], Value::String("a%".to_string()));
+    }
+
+    #[test]
+    fn test_condition() {
+        let condition = Condition::Compare {
+            field: "id".to_string(),
+            op: "=",
+            value: Value::Int(1),
+        }
+        .or(Condition::IsNull {
+            field: "parent".to_string(),
+            negated: false,
+        })
+        .and(!Condition::In {
+            field: "kind".to_string(),
+            values: vec![Value::Int(2)],
+            negated: true,
+        });
+
+        let mut args = Args::new();
+        let format = Cond::new(&mut args).condition(&condition);
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::MySQL, Vec::new()).unwrap();
+        assert_eq!(sql, "((id = ? OR parent IS NULL) AND NOT kind NOT IN (?))");
+        assert_eq!(values, vec![Value::Int(1), Value::Int(2)]);
+    }
+}
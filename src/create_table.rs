@@ -1,254 +1,388 @@
-use std::collections::VecDeque;
-
-// 定义常量枚举，用于标记 SQL 构建的不同阶段
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum InjectionMarker {
-    Init,
-    AfterCreate,
-    AfterDefine,
-    AfterOption,
-}
+use crate::args::{escape, Args};
+use crate::builder::{BuildMode, Builder};
+use crate::dialect::{Feature, KeyModel};
+use crate::error::Error;
+use crate::expr::Expr;
+use crate::flavor::Flavor;
+use crate::injection::{Injection, InjectionMarker};
+use crate::stringbuilder::StringBuilder;
+use crate::value::{ToSqlValue, Value};
+
+// 定义常量，用于标记 SQL 构建的不同阶段
+const CREATE_TABLE_MARKER_INIT: InjectionMarker = 0;
+const CREATE_TABLE_MARKER_AFTER_CREATE: InjectionMarker = 1;
+const CREATE_TABLE_MARKER_AFTER_DEFINE: InjectionMarker = 2;
+const CREATE_TABLE_MARKER_AFTER_OPTION: InjectionMarker = 3;
 
 // CreateTableBuilder 是用于构建 CREATE TABLE 语句的结构体
-struct CreateTableBuilder {
-    verb: String,
+pub struct CreateTableBuilder {
+    temporary: bool,
     if_not_exists: bool,
+    auto_quote: bool,
     table: String,
     defs: Vec<Vec<String>>,
+    primary_key: Vec<String>,
     options: Vec<Vec<String>>,
     args: Args,
     injection: Injection,
     marker: InjectionMarker,
 }
 
+impl Default for CreateTableBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CreateTableBuilder {
     // 创建一个新的 CreateTableBuilder 实例
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self {
-            verb: "CREATE TABLE".to_string(),
+            temporary: false,
             if_not_exists: false,
+            auto_quote: false,
             table: String::new(),
             defs: Vec::new(),
+            primary_key: Vec::new(),
             options: Vec::new(),
             args: Args::new(),
             injection: Injection::new(),
-            marker: InjectionMarker::Init,
+            marker: CREATE_TABLE_MARKER_INIT,
         }
     }
 
     // 设置表名
-    fn create_table(&mut self, table: &str) -> &mut Self {
+    pub fn create_table(&mut self, table: &str) -> &mut Self {
         self.table = escape(table);
-        self.marker = InjectionMarker::AfterCreate;
+        self.marker = CREATE_TABLE_MARKER_AFTER_CREATE;
         self
     }
 
     // 设置临时表名
-    fn create_temp_table(&mut self, table: &str) -> &mut Self {
-        self.verb = "CREATE TEMPORARY TABLE".to_string();
+    pub fn create_temp_table(&mut self, table: &str) -> &mut Self {
+        self.temporary = true;
         self.table = escape(table);
-        self.marker = InjectionMarker::AfterCreate;
+        self.marker = CREATE_TABLE_MARKER_AFTER_CREATE;
         self
     }
 
     // 添加 IF NOT EXISTS 子句
-    fn if_not_exists(&mut self) -> &mut Self {
+    pub fn if_not_exists(&mut self) -> &mut Self {
         self.if_not_exists = true;
         self
     }
 
+    // 开启自动加引号模式
+    // 开启后表名和列定义中的列名仅在是关键字、大小写会被折叠或包含特殊字符时才加引号
+    // 列定义的第一项视为列名，PRIMARY KEY、INDEX 等表级约束和只有一项的定义保持原样
+    pub fn auto_quote(&mut self) -> &mut Self {
+        self.auto_quote = true;
+        self
+    }
+
     // 添加列或索引定义
-    fn define(&mut self, def: Vec<String>) -> &mut Self {
+    pub fn define(&mut self, def: Vec<String>) -> &mut Self {
         self.defs.push(def);
-        self.marker = InjectionMarker::AfterDefine;
+        self.marker = CREATE_TABLE_MARKER_AFTER_DEFINE;
+        self
+    }
+
+    // 设置主键列
+    // 大多数方言在列定义中生成 PRIMARY KEY (...)
+    // Doris 和 StarRocks 在列定义之后生成 UNIQUE KEY(...) 或 PRIMARY KEY(...)，并按主键分桶
+    pub fn primary_key(&mut self, columns: &[&str]) -> &mut Self {
+        self.primary_key = columns.iter().map(|c| escape(c)).collect();
+        self.marker = CREATE_TABLE_MARKER_AFTER_DEFINE;
         self
     }
 
     // 添加表选项
-    fn option(&mut self, opt: Vec<String>) -> &mut Self {
+    pub fn option(&mut self, opt: Vec<String>) -> &mut Self {
         self.options.push(opt);
-        self.marker = InjectionMarker::AfterOption;
+        self.marker = CREATE_TABLE_MARKER_AFTER_OPTION;
         self
     }
 
     // 返回定义的数量
-    fn num_define(&self) -> usize {
+    pub fn num_define(&self) -> usize {
         self.defs.len()
     }
 
     // 返回编译后的 SQL 字符串
-    fn string(&self) -> String {
-        self.build().0
+    pub fn string(&self) -> Result<String, Error> {
+        Ok(self.build()?.0)
     }
 
-    // 编译并返回 SQL 字符串和参数
-    fn build(&self) -> (String, Vec<Box<dyn std::any::Any>>) {
-        self.build_with_flavor(self.args.flavor.clone(), Vec::new())
+    // 编译并返回 SQL 字符串和参数，错误与 Builder::build 一样包装为 Error::Build
+    pub fn build(&self) -> Result<(String, Vec<Value>), Error> {
+        Builder::build(self)
     }
 
     // 使用指定的 Flavor 编译 SQL 字符串和参数
-    fn build_with_flavor(
+    // 开启 auto_quote 时，无法安全加引号的表名返回 InvalidIdentifier
+    // Flavor 不支持临时表或 IF NOT EXISTS 时，严格模式返回 UnsupportedFeature，宽松模式尽量模拟：
+    // Oracle 使用 GLOBAL TEMPORARY TABLE，SQL Server 使用 # 开头的表名并用 OBJECT_ID 判断表是否存在
+    pub fn build_with_flavor(
         &self,
         flavor: Flavor,
-        initial_arg: Vec<Box<dyn std::any::Any>>,
-    ) -> (String, Vec<Box<dyn std::any::Any>>) {
+        initial_arg: Vec<Value>,
+    ) -> Result<(String, Vec<Value>), Error> {
+        let mode = self.args.mode;
+        let temporary_supported = !self.temporary || flavor.check_feature(Feature::TemporaryTable, mode)?;
+        let if_not_exists_supported = !self.if_not_exists || flavor.check_feature(Feature::IfNotExists, mode)?;
+
+        let sqlserver_temp = !temporary_supported && matches!(flavor, Flavor::SQLServer);
+
+        if self.auto_quote && !self.table.is_empty() {
+            flavor.check_identifier(&self.table)?;
+        }
+
+        let table = if sqlserver_temp {
+            // # 是表名的一部分，需要加引号时放在引号之内，例如 [#order]
+            let name = format!("#{}", self.table);
+
+            if self.auto_quote && flavor.needs_quote(&self.table) {
+                flavor.quote(&name)
+            } else {
+                name
+            }
+        } else if self.auto_quote && !self.table.is_empty() {
+            flavor.quote_if_needed(&self.table)
+        } else {
+            self.table.clone()
+        };
+
+        let mut defs = self
+            .defs
+            .iter()
+            .map(|def| match def.split_first() {
+                Some((name, rest)) if self.auto_quote && !rest.is_empty() && !is_table_constraint(name) => {
+                    flavor.check_identifier(name)?;
+                    let mut def = vec![flavor.quote_if_needed(name)];
+                    def.extend(rest.iter().cloned());
+                    Ok(def.join(" "))
+                }
+                _ => Ok(def.join(" ")),
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        let mut key_clause = None;
+
+        if !self.primary_key.is_empty() {
+            let columns = self
+                .primary_key
+                .iter()
+                .map(|column| {
+                    if !self.auto_quote {
+                        return Ok(column.clone());
+                    }
+
+                    flavor.check_identifier(column)?;
+                    Ok(flavor.quote_if_needed(column))
+                })
+                .collect::<Result<Vec<_>, Error>>()?
+                .join(", ");
+
+            match flavor.key_model() {
+                KeyModel::Standard => defs.push(format!("PRIMARY KEY ({})", columns)),
+                KeyModel::UniqueKey => key_clause = Some(format!("UNIQUE KEY({0}) DISTRIBUTED BY HASH({0})", columns)),
+                KeyModel::PrimaryKey => key_clause = Some(format!("PRIMARY KEY({0}) DISTRIBUTED BY HASH({0})", columns)),
+            }
+        }
+
+        let mut verb = if self.temporary { "CREATE TEMPORARY TABLE" } else { "CREATE TABLE" };
+        let mut if_not_exists = self.if_not_exists;
+        let mut guard = None;
+
+        match flavor {
+            Flavor::Oracle if !temporary_supported => verb = "CREATE GLOBAL TEMPORARY TABLE",
+            Flavor::SQLServer => {
+                if sqlserver_temp {
+                    verb = "CREATE TABLE";
+                }
+
+                if !if_not_exists_supported {
+                    let name = if sqlserver_temp { format!("tempdb..{}", table) } else { table.clone() };
+                    guard = Some(format!("IF OBJECT_ID('{}', 'U') IS NULL", name.replace('\'', "''")));
+                    if_not_exists = false;
+                }
+            }
+            _ => {}
+        }
+
         let mut buf = StringBuilder::new();
-        self.injection.write_to(&mut buf, InjectionMarker::Init);
+        self.injection.write_to(&mut buf, CREATE_TABLE_MARKER_INIT);
 
-        if !self.verb.is_empty() {
-            buf.write_leading_string(&self.verb);
+        if let Some(guard) = &guard {
+            buf.write_leading_string(guard);
         }
 
-        if self.if_not_exists {
+        buf.write_leading_string(verb);
+
+        if if_not_exists {
             buf.write_leading_string("IF NOT EXISTS");
         }
 
-        if !self.table.is_empty() {
-            buf.write_leading_string(&self.table);
+        if !table.is_empty() {
+            buf.write_leading_string(&table);
         }
 
-        self.injection.write_to(&mut buf, InjectionMarker::AfterCreate);
+        self.injection.write_to(&mut buf, CREATE_TABLE_MARKER_AFTER_CREATE);
 
-        if !self.defs.is_empty() {
+        if !defs.is_empty() {
             buf.write_leading_string("(");
-
-            let defs: Vec<String> = self.defs.iter().map(|def| def.join(" ")).collect();
             buf.write_strings(&defs, ", ");
-            buf.write_char(')');
+            buf.write_rune(')');
+
+            if let Some(key_clause) = &key_clause {
+                buf.write_leading_string(key_clause);
+            }
 
-            self.injection.write_to(&mut buf, InjectionMarker::AfterDefine);
+            self.injection.write_to(&mut buf, CREATE_TABLE_MARKER_AFTER_DEFINE);
         }
 
         if !self.options.is_empty() {
             let opts: Vec<String> = self.options.iter().map(|opt| opt.join(" ")).collect();
             buf.write_leading_string(&opts.join(", "));
-            self.injection.write_to(&mut buf, InjectionMarker::AfterOption);
+            self.injection.write_to(&mut buf, CREATE_TABLE_MARKER_AFTER_OPTION);
         }
 
-        self.args.compile_with_flavor(&buf.to_string(), flavor, initial_arg)
+        self.args.compile_with_flavor(buf.to_string(), flavor, initial_arg)
     }
 
     // 设置 Flavor
-    fn set_flavor(&mut self, flavor: Flavor) -> Flavor {
+    pub fn set_flavor(&mut self, flavor: Flavor) -> Flavor {
         let old = self.args.flavor.clone();
         self.args.flavor = flavor;
         old
     }
 
     // 返回当前的 Flavor
-    fn flavor(&self) -> &Flavor {
+    pub fn flavor(&self) -> &Flavor {
         &self.args.flavor
     }
 
-    // 添加参数并返回占位符
-    fn var(&mut self, arg: Box<dyn std::any::Any>) -> String {
-        self.args.add(arg)
+    // 设置遇到 Flavor 不支持的特性时的处理方式，返回原来的值
+    pub fn set_build_mode(&mut self, mode: BuildMode) -> BuildMode {
+        std::mem::replace(&mut self.args.mode, mode)
     }
 
-    // 添加任意 SQL 片段
-    fn sql(&mut self, sql: &str) -> &mut Self {
-        self.injection.sql(self.marker, sql);
-        self
+    // 返回当前的处理方式
+    pub fn build_mode(&self) -> BuildMode {
+        self.args.mode
     }
-}
-
-// Args 结构体，用于管理 SQL 参数
-struct Args {
-    flavor: Flavor,
-    // 其他字段和方法
-}
 
-impl Args {
-    fn new() -> Self {
-        Self {
-            flavor: Flavor::Default,
-        }
+    // 添加参数并返回占位符
+    pub fn var(&mut self, arg: impl ToSqlValue) -> String {
+        self.args.add(arg)
     }
 
-    // 使用指定的 Flavor 编译 SQL
-    fn compile_with_flavor(
-        &self,
-        sql: &str,
-        flavor: Flavor,
-        initial_arg: Vec<Box<dyn std::any::Any>>,
-    ) -> (String, Vec<Box<dyn std::any::Any>>) {
-        // 这里实现具体的编译逻辑
-        (sql.to_string(), initial_arg)
+    // 添加表达式并返回占位标记，例如用于 DEFAULT 和 CHECK 中的表达式
+    pub fn expr(&mut self, expr: impl Into<Expr>) -> String {
+        self.args.add_expr(expr)
     }
 
-    // 添加参数并返回占位符
-    fn add(&mut self, arg: Box<dyn std::any::Any>) -> String {
-        // 这里实现占位符逻辑
-        "?".to_string()
+    // 添加任意 SQL 片段
+    pub fn sql(&mut self, sql: &str) -> &mut Self {
+        self.injection.sql(self.marker, sql.to_string());
+        self
     }
 }
 
-// Injection 结构体，用于管理 SQL 注入
-struct Injection {
-    // 字段和方法
+// 判断列定义的第一项是否为表级约束的关键字
+fn is_table_constraint(name: &str) -> bool {
+    const CONSTRAINTS: &[&str] = &[
+        "PRIMARY", "UNIQUE", "KEY", "INDEX", "CONSTRAINT", "FOREIGN", "CHECK", "FULLTEXT", "SPATIAL",
+    ];
+    let first = name.split_whitespace().next().unwrap_or_default();
+    CONSTRAINTS.iter().any(|c| c.eq_ignore_ascii_case(first))
 }
 
-impl Injection {
-    fn new() -> Self {
-        Self {
-            // 初始化
-        }
-    }
-
-    // 将注入内容写入 StringBuilder
-    fn write_to(&self, buf: &mut StringBuilder, marker: InjectionMarker) {
-        // 实现注入逻辑
+impl Builder for CreateTableBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: Vec<Value>) -> Result<(String, Vec<Value>), Error> {
+        CreateTableBuilder::build_with_flavor(self, flavor, initial_arg)
     }
 
-    // 添加 SQL 片段
-    fn sql(&mut self, marker: InjectionMarker, sql: &str) {
-        // 实现 SQL 注入逻辑
+    fn flavor(&self) -> Flavor {
+        self.args.flavor.clone()
     }
 }
 
-// Flavor 枚举，用于表示 SQL 方言
-#[derive(Clone)]
-enum Flavor {
-    Default,
-    // 其他方言
-}
-
-// StringBuilder 结构体，用于构建 SQL 字符串
-struct StringBuilder {
-    buffer: VecDeque<String>,
-}
-
-impl StringBuilder {
-    fn new() -> Self {
-        StringBuilder {
-            buffer: VecDeque::new(),
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_quote() {
+        let mut ctb = Flavor::MySQL.new_create_table_builder();
+        ctb.create_table("order")
+            .auto_quote()
+            .define(vec!["id".to_string(), "INT".to_string()])
+            .define(vec!["user".to_string(), "VARCHAR(20)".to_string()])
+            .define(vec!["PRIMARY KEY".to_string(), "(id)".to_string()]);
+        assert_eq!(
+            ctb.string().unwrap(),
+            "CREATE TABLE `order` (id INT, `user` VARCHAR(20), PRIMARY KEY (id))"
+        );
+
+        assert_eq!(
+            ctb.pretty().unwrap(),
+            "CREATE TABLE `order` (\n  id INT,\n  `user` VARCHAR(20),\n  PRIMARY KEY (id)\n)"
+        );
+
+        let (sql, _) = ctb.build_with_flavor(Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(sql, "CREATE TABLE \"order\" (id INT, \"user\" VARCHAR(20), PRIMARY KEY (id))");
+
+        ctb.define(vec!["we`ird".to_string(), "INT".to_string()]);
+        assert!(matches!(ctb.build().unwrap_err().root(), Error::InvalidIdentifier { .. }));
     }
 
-    // 添加前导字符串
-    fn write_leading_string(&mut self, s: &str) {
-        self.buffer.push_back(s.to_string());
+    #[test]
+    fn test_primary_key() {
+        let mut ctb = Flavor::MySQL.new_create_table_builder();
+        ctb.create_table("t")
+            .define(vec!["id".to_string(), "BIGINT".to_string()])
+            .define(vec!["name".to_string(), "VARCHAR(20)".to_string()])
+            .primary_key(&["id"]);
+
+        assert_eq!(ctb.string().unwrap(), "CREATE TABLE t (id BIGINT, name VARCHAR(20), PRIMARY KEY (id))");
+
+        let (sql, _) = ctb.build_with_flavor(Flavor::Doris, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            "CREATE TABLE t (id BIGINT, name VARCHAR(20)) UNIQUE KEY(id) DISTRIBUTED BY HASH(id)"
+        );
+
+        let (sql, _) = ctb.build_with_flavor(Flavor::StarRocks, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            "CREATE TABLE t (id BIGINT, name VARCHAR(20)) PRIMARY KEY(id) DISTRIBUTED BY HASH(id)"
+        );
     }
 
-    // 添加多个字符串，用分隔符连接
-    fn write_strings(&mut self, strings: &[String], sep: &str) {
-        let joined = strings.join(sep);
-        self.buffer.push_back(joined);
-    }
+    #[test]
+    fn test_to_debug_string() {
+        let mut ctb = Flavor::PostgreSQL.new_create_table_builder();
+        let status = ctb.var("it's new");
+        ctb.create_table("t").define(vec!["status".to_string(), format!("TEXT DEFAULT {}", status)]);
 
-    // 添加字符
-    fn write_char(&mut self, c: char) {
-        self.buffer.push_back(c.to_string());
+        assert_eq!(ctb.build().unwrap().0, "CREATE TABLE t (status TEXT DEFAULT $1)");
+        assert_eq!(ctb.to_debug_string().unwrap(), "CREATE TABLE t (status TEXT DEFAULT 'it''s new')");
     }
 
-    // 将缓冲区内容转换为字符串
-    fn to_string(&self) -> String {
-        self.buffer.iter().map(|s| s.as_str()).collect()
+    #[test]
+    fn test_sqlserver_temp_table() {
+        let mut ctb = Flavor::SQLServer.new_create_table_builder();
+        ctb.create_temp_table("order").auto_quote().define(vec!["id".to_string(), "INT".to_string()]);
+        assert_eq!(ctb.string().unwrap(), "CREATE TABLE \"#order\" (id INT)");
+
+        ctb.if_not_exists();
+        assert_eq!(
+            ctb.string().unwrap(),
+            "IF OBJECT_ID('tempdb..\"#order\"', 'U') IS NULL CREATE TABLE \"#order\" (id INT)"
+        );
+
+        let mut ctb = Flavor::SQLServer.new_create_table_builder();
+        ctb.create_temp_table("t").auto_quote().define(vec!["id".to_string(), "INT".to_string()]);
+        assert_eq!(ctb.string().unwrap(), "CREATE TABLE #t (id INT)");
     }
 }
-
-// 辅助函数：转义字符串
-fn escape(s: &str) -> String {
-    // 实现转义逻辑
-    s.to_string()
-}
\ No newline at end of file
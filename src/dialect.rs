@@ -0,0 +1,706 @@
+
+use std::fmt;
+
+use crate::error::Error;
+use crate::interpolate::encode_value;
+use crate::keywords;
+use crate::value::Value;
+
+// 占位符风格
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlaceholderStyle {
+    // 所有参数都使用 ?
+    Question,
+    // 前缀加从 1 开始的编号，例如 $1、@p1、:1
+    Numbered(&'static str),
+}
+
+// 未加引号的标识符在数据库中的大小写折叠方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentifierCase {
+    // 保持原样或不区分大小写
+    Preserve,
+    // 折叠为小写
+    Lower,
+    // 折叠为大写
+    Upper,
+}
+
+// 插入或更新（upsert）的语法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsertSyntax {
+    // INSERT ... ON DUPLICATE KEY UPDATE
+    OnDuplicateKeyUpdate,
+    // INSERT ... ON CONFLICT ... DO UPDATE
+    OnConflict,
+    // INSERT OR REPLACE INTO
+    InsertOrReplace,
+    // MERGE INTO ... USING ...
+    Merge,
+    // 普通 INSERT 会覆盖主键相同的行
+    KeyedInsert,
+    // 不支持
+    Unsupported,
+}
+
+// 建表时声明主键的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyModel {
+    // 在列定义中声明 PRIMARY KEY (...)
+    Standard,
+    // Doris 的 Unique 模型：列定义之后的 UNIQUE KEY(...)，并且必须按主键分桶
+    UniqueKey,
+    // StarRocks 的主键模型：列定义之后的 PRIMARY KEY(...)，并且必须按主键分桶
+    PrimaryKey,
+}
+
+// 并非所有方言都支持的 SQL 特性
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Feature {
+    // INSERT 和 DELETE 的 RETURNING 子句
+    Returning,
+    // UPDATE 的 RETURNING 子句，MariaDB 只支持 INSERT 和 DELETE 的 RETURNING
+    UpdateReturning,
+    // FULL OUTER JOIN
+    FullJoin,
+    // INSERT ... ON CONFLICT DO NOTHING
+    OnConflict,
+    // CREATE TABLE IF NOT EXISTS
+    IfNotExists,
+    // CREATE TEMPORARY TABLE
+    TemporaryTable,
+    // 窗口函数的 OVER 子句
+    WindowFunction,
+    // 聚合函数的 FILTER (WHERE ...) 子句
+    AggregateFilter,
+    // 全文检索的匹配条件，例如 MATCH ... AGAINST、@@、CONTAINS
+    FullTextSearch,
+    // 全文检索的相关度，例如 ts_rank
+    FullTextRank,
+    // 数组类型的运算，例如 = ANY、&&、has
+    Array,
+    // 判断 JSON 文档是否包含另一个文档，见 json::json_contains
+    JsonContains,
+    // JSON 数组的长度，见 json::json_array_length
+    JsonArrayLength,
+    // 按单位截断日期时间，见 func::date_trunc
+    DateTrunc,
+    // 日期时间加减，见 func::date_add
+    DateAdd,
+}
+
+impl fmt::Display for Feature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Feature::Returning => "RETURNING",
+            Feature::UpdateReturning => "UPDATE RETURNING",
+            Feature::FullJoin => "FULL JOIN",
+            Feature::OnConflict => "ON CONFLICT",
+            Feature::IfNotExists => "IF NOT EXISTS",
+            Feature::TemporaryTable => "TEMPORARY TABLE",
+            Feature::WindowFunction => "OVER",
+            Feature::AggregateFilter => "FILTER",
+            Feature::FullTextSearch => "FULL-TEXT SEARCH",
+            Feature::FullTextRank => "FULL-TEXT RANK",
+            Feature::Array => "ARRAY",
+            Feature::JsonContains => "JSON_CONTAINS",
+            Feature::JsonArrayLength => "JSON_ARRAY_LENGTH",
+            Feature::DateTrunc => "DATE_TRUNC",
+            Feature::DateAdd => "DATE_ADD",
+        };
+
+        f.write_str(name)
+    }
+}
+
+// 方言的词法和字面量能力
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Capabilities {
+    // 字符串中的 \ 是转义字符
+    pub backslash_escape: bool,
+    // "..." 是字符串而不是标识符
+    pub double_quote_string: bool,
+    // `...` 是标识符
+    pub backtick_ident: bool,
+    // [...] 是标识符
+    pub bracket_ident: bool,
+    // # 开始单行注释
+    pub hash_comment: bool,
+    // 支持 $tag$...$tag$ 字符串
+    pub dollar_quote: bool,
+    // 支持 E'...' 转义字符串
+    pub escape_string: bool,
+    // 块注释可以嵌套
+    pub nested_comments: bool,
+    // 布尔值编码为 1 和 0 而不是 TRUE 和 FALSE
+    pub numeric_booleans: bool,
+    // 字符串字面量使用 N'...' 形式
+    pub national_strings: bool,
+}
+
+impl Capabilities {
+    // 不具备任何额外能力
+    pub const NONE: Capabilities = Capabilities {
+        backslash_escape: false,
+        double_quote_string: false,
+        backtick_ident: false,
+        bracket_ident: false,
+        hash_comment: false,
+        dollar_quote: false,
+        escape_string: false,
+        nested_comments: false,
+        numeric_booleans: false,
+        national_strings: false,
+    };
+}
+
+// 方言对参数数量的限制，None 表示没有限制
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Limits {
+    // 一条语句中参数的最大数量
+    pub max_params: Option<usize>,
+    // 一个 IN 列表中值的最大数量
+    pub max_in_list: Option<usize>,
+}
+
+impl Limits {
+    // 没有任何限制
+    pub const NONE: Limits = Limits {
+        max_params: None,
+        max_in_list: None,
+    };
+}
+
+// Dialect 描述一种 SQL 方言
+// 内置的 Flavor 都由 Dialect 实现，也可以通过 Flavor::custom 注册自定义方言
+pub trait Dialect: Send + Sync {
+    // 返回方言名称
+    fn name(&self) -> &str;
+
+    // 返回占位符风格
+    fn placeholder_style(&self) -> PlaceholderStyle;
+
+    // 返回第 n 个参数（从 1 开始）的占位符
+    fn placeholder(&self, n: usize) -> String {
+        match self.placeholder_style() {
+            PlaceholderStyle::Question => "?".to_string(),
+            PlaceholderStyle::Numbered(prefix) => format!("{}{}", prefix, n),
+        }
+    }
+
+    // 为名称加引号
+    fn quote(&self, name: &str) -> String;
+
+    // 返回保留关键字列表（不含 keywords::COMMON 中的通用关键字）
+    fn reserved_keywords(&self) -> &'static [&'static str] {
+        &[]
+    }
+
+    // 返回未加引号的标识符的大小写折叠方式
+    fn identifier_case(&self) -> IdentifierCase {
+        IdentifierCase::Preserve
+    }
+
+    // 返回插入或更新的语法
+    fn upsert_syntax(&self) -> UpsertSyntax {
+        UpsertSyntax::Unsupported
+    }
+
+    // 返回建表时声明主键的方式
+    fn key_model(&self) -> KeyModel {
+        KeyModel::Standard
+    }
+
+    // 返回方言支持的特性
+    fn features(&self) -> &'static [Feature] {
+        &[]
+    }
+
+    // 判断是否支持指定的特性
+    fn supports(&self, feature: Feature) -> bool {
+        self.features().contains(&feature)
+    }
+
+    // 返回词法和字面量能力
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::NONE
+    }
+
+    // 返回参数数量的限制
+    fn limits(&self) -> Limits {
+        Limits::NONE
+    }
+
+    // 将参数编码为 SQL 字面量
+    fn encode_literal(&self, buf: &mut String, value: &Value) -> Result<(), Error> {
+        encode_value(buf, value, &self.capabilities())
+    }
+}
+
+// 内置方言，所有差异都以数据描述
+pub(crate) struct BuiltinDialect {
+    name: &'static str,
+    placeholder: PlaceholderStyle,
+    quote: (char, char),
+    keywords: &'static [&'static str],
+    identifier_case: IdentifierCase,
+    upsert: UpsertSyntax,
+    key_model: KeyModel,
+    features: &'static [Feature],
+    capabilities: Capabilities,
+    limits: Limits,
+}
+
+impl Dialect for BuiltinDialect {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn placeholder_style(&self) -> PlaceholderStyle {
+        self.placeholder
+    }
+
+    fn quote(&self, name: &str) -> String {
+        format!("{}{}{}", self.quote.0, name, self.quote.1)
+    }
+
+    fn reserved_keywords(&self) -> &'static [&'static str] {
+        self.keywords
+    }
+
+    fn identifier_case(&self) -> IdentifierCase {
+        self.identifier_case
+    }
+
+    fn upsert_syntax(&self) -> UpsertSyntax {
+        self.upsert
+    }
+
+    fn key_model(&self) -> KeyModel {
+        self.key_model
+    }
+
+    fn features(&self) -> &'static [Feature] {
+        self.features
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.capabilities
+    }
+
+    fn limits(&self) -> Limits {
+        self.limits
+    }
+}
+
+// MySQL 系列方言共用的描述
+const MYSQL_BASE: BuiltinDialect = BuiltinDialect {
+    name: "MySQL",
+    placeholder: PlaceholderStyle::Question,
+    quote: ('`', '`'),
+    keywords: keywords::MYSQL,
+    identifier_case: IdentifierCase::Preserve,
+    upsert: UpsertSyntax::OnDuplicateKeyUpdate,
+    key_model: KeyModel::Standard,
+    features: &[
+        Feature::IfNotExists,
+        Feature::TemporaryTable,
+        Feature::WindowFunction,
+        Feature::FullTextSearch,
+        Feature::FullTextRank,
+        Feature::DateTrunc,
+        Feature::DateAdd,
+        Feature::JsonContains,
+        Feature::JsonArrayLength,
+    ],
+    capabilities: Capabilities {
+        backslash_escape: true,
+        double_quote_string: true,
+        backtick_ident: true,
+        hash_comment: true,
+        ..Capabilities::NONE
+    },
+    limits: Limits {
+        max_params: Some(65535),
+        ..Limits::NONE
+    },
+};
+
+pub(crate) static MYSQL: BuiltinDialect = MYSQL_BASE;
+
+// MySQL 8.0 之前的版本，不支持窗口函数
+pub(crate) static MYSQL_57: BuiltinDialect = BuiltinDialect {
+    name: "MySQL57",
+    features: &[
+        Feature::IfNotExists,
+        Feature::TemporaryTable,
+        Feature::FullTextSearch,
+        Feature::FullTextRank,
+        Feature::DateTrunc,
+        Feature::DateAdd,
+        Feature::JsonContains,
+        Feature::JsonArrayLength,
+    ],
+    ..MYSQL_BASE
+};
+
+pub(crate) static POSTGRESQL: BuiltinDialect = BuiltinDialect {
+    name: "PostgreSQL",
+    placeholder: PlaceholderStyle::Numbered("$"),
+    quote: ('"', '"'),
+    keywords: keywords::POSTGRESQL,
+    identifier_case: IdentifierCase::Lower,
+    upsert: UpsertSyntax::OnConflict,
+    key_model: KeyModel::Standard,
+    features: &[
+        Feature::Returning,
+        Feature::UpdateReturning,
+        Feature::FullJoin,
+        Feature::OnConflict,
+        Feature::IfNotExists,
+        Feature::TemporaryTable,
+        Feature::WindowFunction,
+        Feature::AggregateFilter,
+        Feature::FullTextSearch,
+        Feature::FullTextRank,
+        Feature::Array,
+        Feature::DateTrunc,
+        Feature::DateAdd,
+        Feature::JsonContains,
+        Feature::JsonArrayLength,
+    ],
+    capabilities: Capabilities {
+        dollar_quote: true,
+        escape_string: true,
+        nested_comments: true,
+        ..Capabilities::NONE
+    },
+    limits: Limits {
+        max_params: Some(65535),
+        ..Limits::NONE
+    },
+};
+
+pub(crate) static SQLITE: BuiltinDialect = BuiltinDialect {
+    name: "SQLite",
+    placeholder: PlaceholderStyle::Question,
+    quote: ('"', '"'),
+    keywords: keywords::SQLITE,
+    identifier_case: IdentifierCase::Preserve,
+    upsert: UpsertSyntax::OnConflict,
+    key_model: KeyModel::Standard,
+    features: &[
+        Feature::Returning,
+        Feature::UpdateReturning,
+        Feature::FullJoin,
+        Feature::OnConflict,
+        Feature::IfNotExists,
+        Feature::TemporaryTable,
+        Feature::WindowFunction,
+        Feature::AggregateFilter,
+        Feature::FullTextSearch,
+        Feature::FullTextRank,
+        Feature::DateTrunc,
+        Feature::DateAdd,
+        Feature::JsonContains,
+        Feature::JsonArrayLength,
+    ],
+    capabilities: Capabilities {
+        backtick_ident: true,
+        bracket_ident: true,
+        numeric_booleans: true,
+        ..Capabilities::NONE
+    },
+    limits: Limits {
+        max_params: Some(32766),
+        ..Limits::NONE
+    },
+};
+
+pub(crate) static SQLSERVER: BuiltinDialect = BuiltinDialect {
+    name: "SQLServer",
+    placeholder: PlaceholderStyle::Numbered("@p"),
+    quote: ('"', '"'),
+    keywords: keywords::SQLSERVER,
+    identifier_case: IdentifierCase::Preserve,
+    upsert: UpsertSyntax::Merge,
+    key_model: KeyModel::Standard,
+    features: &[
+        Feature::FullJoin,
+        Feature::WindowFunction,
+        Feature::FullTextSearch,
+        Feature::DateTrunc,
+        Feature::DateAdd,
+        Feature::JsonContains,
+        Feature::JsonArrayLength,
+    ],
+    capabilities: Capabilities {
+        bracket_ident: true,
+        numeric_booleans: true,
+        national_strings: true,
+        ..Capabilities::NONE
+    },
+    limits: Limits {
+        max_params: Some(2100),
+        ..Limits::NONE
+    },
+};
+
+pub(crate) static CQL: BuiltinDialect = BuiltinDialect {
+    name: "CQL",
+    placeholder: PlaceholderStyle::Question,
+    quote: ('\'', '\''),
+    keywords: keywords::CQL,
+    identifier_case: IdentifierCase::Preserve,
+    upsert: UpsertSyntax::KeyedInsert,
+    key_model: KeyModel::Standard,
+    features: &[Feature::IfNotExists],
+    capabilities: Capabilities::NONE,
+    limits: Limits::NONE,
+};
+
+pub(crate) static CLICKHOUSE: BuiltinDialect = BuiltinDialect {
+    name: "ClickHouse",
+    placeholder: PlaceholderStyle::Question,
+    quote: ('`', '`'),
+    keywords: keywords::CLICKHOUSE,
+    identifier_case: IdentifierCase::Preserve,
+    upsert: UpsertSyntax::KeyedInsert,
+    key_model: KeyModel::Standard,
+    features: &[
+        Feature::FullJoin,
+        Feature::IfNotExists,
+        Feature::TemporaryTable,
+        Feature::WindowFunction,
+        Feature::Array,
+        Feature::DateTrunc,
+        Feature::DateAdd,
+        Feature::JsonArrayLength,
+    ],
+    capabilities: Capabilities {
+        backslash_escape: true,
+        backtick_ident: true,
+        ..Capabilities::NONE
+    },
+    limits: Limits::NONE,
+};
+
+pub(crate) static PRESTO: BuiltinDialect = BuiltinDialect {
+    name: "Presto",
+    placeholder: PlaceholderStyle::Question,
+    quote: ('"', '"'),
+    keywords: keywords::PRESTO,
+    identifier_case: IdentifierCase::Preserve,
+    upsert: UpsertSyntax::Unsupported,
+    key_model: KeyModel::Standard,
+    features: &[
+        Feature::FullJoin,
+        Feature::IfNotExists,
+        Feature::WindowFunction,
+        Feature::DateTrunc,
+        Feature::DateAdd,
+        Feature::JsonArrayLength,
+    ],
+    capabilities: Capabilities::NONE,
+    limits: Limits::NONE,
+};
+
+pub(crate) static ORACLE: BuiltinDialect = BuiltinDialect {
+    name: "Oracle",
+    placeholder: PlaceholderStyle::Numbered(":"),
+    quote: ('"', '"'),
+    keywords: keywords::ORACLE,
+    identifier_case: IdentifierCase::Upper,
+    upsert: UpsertSyntax::Merge,
+    key_model: KeyModel::Standard,
+    features: &[
+        Feature::FullJoin,
+        Feature::WindowFunction,
+        Feature::FullTextSearch,
+        Feature::DateTrunc,
+        Feature::DateAdd,
+        Feature::JsonArrayLength,
+    ],
+    capabilities: Capabilities {
+        numeric_booleans: true,
+        ..Capabilities::NONE
+    },
+    limits: Limits {
+        max_in_list: Some(1000),
+        ..Limits::NONE
+    },
+};
+
+pub(crate) static INFORMIX: BuiltinDialect = BuiltinDialect {
+    name: "Informix",
+    placeholder: PlaceholderStyle::Question,
+    quote: ('"', '"'),
+    keywords: keywords::INFORMIX,
+    identifier_case: IdentifierCase::Preserve,
+    upsert: UpsertSyntax::Merge,
+    key_model: KeyModel::Standard,
+    features: &[
+        Feature::FullJoin,
+        Feature::IfNotExists,
+        Feature::WindowFunction,
+        Feature::DateTrunc,
+        Feature::DateAdd,
+    ],
+    capabilities: Capabilities::NONE,
+    limits: Limits::NONE,
+};
+
+pub(crate) static DUCKDB: BuiltinDialect = BuiltinDialect {
+    name: "DuckDB",
+    placeholder: PlaceholderStyle::Numbered("$"),
+    quote: ('"', '"'),
+    keywords: keywords::DUCKDB,
+    identifier_case: IdentifierCase::Preserve,
+    upsert: UpsertSyntax::InsertOrReplace,
+    key_model: KeyModel::Standard,
+    features: &[
+        Feature::Returning,
+        Feature::UpdateReturning,
+        Feature::FullJoin,
+        Feature::IfNotExists,
+        Feature::TemporaryTable,
+        Feature::WindowFunction,
+        Feature::AggregateFilter,
+        Feature::OnConflict,
+        Feature::DateTrunc,
+        Feature::DateAdd,
+        Feature::JsonContains,
+        Feature::JsonArrayLength,
+    ],
+    capabilities: Capabilities {
+        dollar_quote: true,
+        escape_string: true,
+        ..Capabilities::NONE
+    },
+    limits: Limits::NONE,
+};
+
+pub(crate) static SNOWFLAKE: BuiltinDialect = BuiltinDialect {
+    name: "Snowflake",
+    placeholder: PlaceholderStyle::Question,
+    quote: ('"', '"'),
+    keywords: keywords::SNOWFLAKE,
+    identifier_case: IdentifierCase::Upper,
+    upsert: UpsertSyntax::Merge,
+    key_model: KeyModel::Standard,
+    features: &[
+        Feature::FullJoin,
+        Feature::IfNotExists,
+        Feature::TemporaryTable,
+        Feature::WindowFunction,
+        Feature::DateTrunc,
+        Feature::DateAdd,
+        Feature::JsonArrayLength,
+    ],
+    capabilities: Capabilities {
+        backslash_escape: true,
+        dollar_quote: true,
+        ..Capabilities::NONE
+    },
+    limits: Limits::NONE,
+};
+
+pub(crate) static BIGQUERY: BuiltinDialect = BuiltinDialect {
+    name: "BigQuery",
+    placeholder: PlaceholderStyle::Numbered("@p"),
+    quote: ('`', '`'),
+    keywords: keywords::BIGQUERY,
+    identifier_case: IdentifierCase::Preserve,
+    upsert: UpsertSyntax::Merge,
+    key_model: KeyModel::Standard,
+    features: &[
+        Feature::FullJoin,
+        Feature::IfNotExists,
+        Feature::TemporaryTable,
+        Feature::WindowFunction,
+        Feature::DateTrunc,
+        Feature::DateAdd,
+        Feature::JsonArrayLength,
+    ],
+    capabilities: Capabilities {
+        backslash_escape: true,
+        double_quote_string: true,
+        backtick_ident: true,
+        hash_comment: true,
+        ..Capabilities::NONE
+    },
+    limits: Limits::NONE,
+};
+
+pub(crate) static MARIADB: BuiltinDialect = BuiltinDialect {
+    name: "MariaDB",
+    placeholder: PlaceholderStyle::Question,
+    quote: ('`', '`'),
+    keywords: keywords::MYSQL,
+    identifier_case: IdentifierCase::Preserve,
+    upsert: UpsertSyntax::OnDuplicateKeyUpdate,
+    key_model: KeyModel::Standard,
+    features: &[
+        Feature::Returning,
+        Feature::IfNotExists,
+        Feature::TemporaryTable,
+        Feature::WindowFunction,
+        Feature::FullTextSearch,
+        Feature::FullTextRank,
+        Feature::DateTrunc,
+        Feature::DateAdd,
+        Feature::JsonContains,
+        Feature::JsonArrayLength,
+    ],
+    capabilities: Capabilities {
+        backslash_escape: true,
+        double_quote_string: true,
+        backtick_ident: true,
+        hash_comment: true,
+        ..Capabilities::NONE
+    },
+    limits: Limits {
+        max_params: Some(65535),
+        ..Limits::NONE
+    },
+};
+
+// Doris 和 StarRocks 使用 MySQL 协议，建表时的主键模型不同：
+// Doris 使用 Unique 模型的 UNIQUE KEY(...)，StarRocks 使用主键模型的 PRIMARY KEY(...)
+const DORIS_FAMILY: BuiltinDialect = BuiltinDialect {
+    name: "Doris",
+    placeholder: PlaceholderStyle::Question,
+    quote: ('`', '`'),
+    keywords: keywords::DORIS,
+    identifier_case: IdentifierCase::Preserve,
+    upsert: UpsertSyntax::KeyedInsert,
+    key_model: KeyModel::UniqueKey,
+    features: &[
+        Feature::FullJoin,
+        Feature::IfNotExists,
+        Feature::WindowFunction,
+        Feature::DateTrunc,
+        Feature::DateAdd,
+        Feature::JsonContains,
+        Feature::JsonArrayLength,
+    ],
+    capabilities: Capabilities {
+        backslash_escape: true,
+        double_quote_string: true,
+        backtick_ident: true,
+        hash_comment: true,
+        ..Capabilities::NONE
+    },
+    limits: Limits {
+        max_params: Some(65535),
+        ..Limits::NONE
+    },
+};
+
+pub(crate) static DORIS: BuiltinDialect = DORIS_FAMILY;
+
+pub(crate) static STARROCKS: BuiltinDialect = BuiltinDialect {
+    name: "StarRocks",
+    key_model: KeyModel::PrimaryKey,
+    ..DORIS_FAMILY
+};
@@ -1,29 +1,109 @@
 use std::fmt;
 
+use crate::dialect::Feature;
+use crate::flavor::Flavor;
+
 #[derive(Debug)]
 pub enum Error {
-    NotImplemented,
-    MissingArgs,
-    UnsupportedArgs,
-    InvalidUtf8
+    // 构造器编译失败，builder 为构造器的类型名，source 为具体的错误，由 Builder::build 包装
+    Build { builder: &'static str, source: Box<Error> },
+    // 占位符引用的参数不存在
+    // expected 为需要的参数个数，got 为实际的参数个数，at 为占位符的字节位置：
+    // 编译构造器时是在包含 $0、${name} 等占位标记的格式字符串中的位置，内联参数时是在 SQL 中的位置
+    MissingArgs { expected: usize, got: usize, at: usize },
+    // 第 index 个参数无法内联或绑定，kind 为参数的类型
+    UnsupportedArgs { index: usize, kind: &'static str },
+    // 参数的数量 count 超过了方言允许的最大数量 max
+    TooManyArgs { count: usize, max: usize },
+    // 方言不支持构造器用到的特性
+    UnsupportedFeature { flavor: Flavor, feature: Feature },
+    // 集成的库 target 不支持该方言，例如 sea_query 没有对应的后端
+    UnsupportedFlavor { flavor: Flavor, target: &'static str },
+    // 表达式无法与 sea_query 的表达式互相转换，kind 为无法转换的节点
+    UnsupportedExpr { kind: &'static str },
+    // 命名参数与自动命名的位置参数同名
+    DuplicateArgName { name: String },
+    // 标识符无法安全地加引号
+    InvalidIdentifier { name: String },
+    // JSON 路径的格式不正确
+    InvalidJsonPath { path: String },
+    InvalidUtf8,
+    InvalidFlavor(String),
+    // 数据库驱动返回的错误
+    Database(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl Error {
+    // 返回 Build 包装的具体错误，其他错误返回自身
+    pub fn root(&self) -> &Error {
+        match self {
+            Error::Build { source, .. } => source.root(),
+            err => err,
+        }
+    }
+
+    // 为 UnsupportedArgs 补充参数的下标，其他错误原样返回
+    pub(crate) fn at_index(self, index: usize) -> Self {
+        match self {
+            Error::UnsupportedArgs { kind, .. } => Error::UnsupportedArgs { index, kind },
+            err => err,
+        }
+    }
 }
 
 // 实现 fmt::Display trait 来格式化错误消息
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Error::NotImplemented => {
-                write!(f, "interpolation for this flavor is not implemented")
+            Error::Build { builder, ref source } => {
+                write!(f, "{}: {}", builder, source)
+            }
+            Error::MissingArgs { expected, got, at } => {
+                write!(f, "not enough args: placeholder at {} needs {} args, got {}", at, expected, got)
+            }
+            Error::UnsupportedArgs { index, kind } => {
+                write!(f, "unsupported arg #{} of type {}", index, kind)
+            }
+            Error::TooManyArgs { count, max } => {
+                write!(f, "too many args: {} exceeds the limit of {}", count, max)
             }
-            Error::MissingArgs => {
-                write!(f, "not enough args when interpolating")
+            Error::UnsupportedFeature { ref flavor, feature } => {
+                write!(f, "{} is not supported by {}", feature, flavor)
             }
-            Error::UnsupportedArgs => {
-                write!(f, "unsupported args when interpolating")
+            Error::UnsupportedFlavor { ref flavor, target } => {
+                write!(f, "{} does not support {}", target, flavor)
+            }
+            Error::UnsupportedExpr { kind } => {
+                write!(f, "unsupported expr: {}", kind)
+            }
+            Error::DuplicateArgName { ref name } => {
+                write!(f, "arg name {:?} conflicts with a positional arg", name)
+            }
+            Error::InvalidIdentifier { ref name } => {
+                write!(f, "invalid identifier: {:?}", name)
+            }
+            Error::InvalidJsonPath { ref path } => {
+                write!(f, "invalid json path: {:?}", path)
             }
             Error::InvalidUtf8 => {
                 write!(f, "invalid utf8")
             }
+            Error::InvalidFlavor(ref name) => {
+                write!(f, "invalid flavor: {}", name)
+            }
+            Error::Database(ref err) => {
+                write!(f, "database error: {}", err)
+            }
         }
     }
-}
\ No newline at end of file
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Build { source, .. } => Some(source.as_ref()),
+            Error::Database(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,880 @@
+use std::fmt;
+use std::ops::{Add, BitAnd, BitOr, Div, Mul, Neg, Not, Rem, Sub};
+use std::sync::Arc;
+
+use crate::in_list;
+use crate::args::{escape, Args};
+use crate::builder::{BuildMode, Builder};
+use crate::column::Column;
+use crate::cond::Condition;
+use crate::dialect::Feature;
+use crate::error::Error;
+use crate::flavor::Flavor;
+use crate::func::Function;
+use crate::stringbuilder::StringBuilder;
+use crate::value::{ToSqlValue, Value};
+use crate::window::Window;
+
+// 运算符的优先级，数值越大结合得越紧
+const PREC_OR: u8 = 1;
+const PREC_AND: u8 = 2;
+const PREC_NOT: u8 = 3;
+const PREC_COMPARE: u8 = 4;
+const PREC_CONCAT: u8 = 5;
+const PREC_ADD: u8 = 6;
+const PREC_MUL: u8 = 7;
+const PREC_NEG: u8 = 8;
+const PREC_ATOM: u8 = 9;
+
+// 一元运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    // NOT expr
+    Not,
+    // -expr
+    Neg,
+    // expr IS NULL
+    IsNull,
+    // expr IS NOT NULL
+    IsNotNull,
+}
+
+// 二元运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    And,
+    Or,
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Like,
+    NotLike,
+    In,
+    NotIn,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    // 字符串连接 ||
+    Concat,
+    // 其他运算符，例如 ->、@>，与 || 的优先级相同并且左结合
+    Other(&'static str),
+}
+
+impl BinaryOp {
+    // 返回运算符的 SQL 写法
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BinaryOp::And => "AND",
+            BinaryOp::Or => "OR",
+            BinaryOp::Eq => "=",
+            BinaryOp::Ne => "<>",
+            BinaryOp::Gt => ">",
+            BinaryOp::Ge => ">=",
+            BinaryOp::Lt => "<",
+            BinaryOp::Le => "<=",
+            BinaryOp::Like => "LIKE",
+            BinaryOp::NotLike => "NOT LIKE",
+            BinaryOp::In => "IN",
+            BinaryOp::NotIn => "NOT IN",
+            BinaryOp::Add => "+",
+            BinaryOp::Sub => "-",
+            BinaryOp::Mul => "*",
+            BinaryOp::Div => "/",
+            BinaryOp::Mod => "%",
+            BinaryOp::Concat => "||",
+            BinaryOp::Other(op) => op,
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            BinaryOp::Or => PREC_OR,
+            BinaryOp::And => PREC_AND,
+            BinaryOp::Concat | BinaryOp::Other(_) => PREC_CONCAT,
+            BinaryOp::Add | BinaryOp::Sub => PREC_ADD,
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod => PREC_MUL,
+            _ => PREC_COMPARE,
+        }
+    }
+
+    // 满足结合律的运算符，右侧同优先级的表达式不需要加括号
+    fn is_associative(&self) -> bool {
+        matches!(
+            self,
+            BinaryOp::And | BinaryOp::Or | BinaryOp::Add | BinaryOp::Mul | BinaryOp::Concat
+        )
+    }
+}
+
+// 子查询，编译时使用外层语句的 Flavor 构造，参数接着外层的参数编号
+#[derive(Clone)]
+pub struct Subquery(Arc<dyn Builder + Send + Sync>);
+
+impl fmt::Debug for Subquery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Subquery(..)")
+    }
+}
+
+// 只有指向同一个构造器的子查询才相等
+impl PartialEq for Subquery {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(Arc::as_ptr(&self.0) as *const (), Arc::as_ptr(&other.0) as *const ())
+    }
+}
+
+// Expr 是 SQL 表达式的语法树
+// 通过 Args::add_expr 得到占位标记后，可以用在构造器接受字符串的任何地方，编译时再按目标 Flavor 渲染
+// 渲染之前可以直接匹配各个节点，或者用 walk 和 transform 检查、改写表达式
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    // 列名，形如 table.column 的限定名称会逐段按需加引号，* 原样输出
+    Column(String),
+    // 绑定为参数的值
+    Value(Value),
+    // 原样输出的 SQL 片段，作为操作数时不会加括号，需要时应自行加上
+    Raw(String),
+    Unary { op: UnaryOp, expr: Box<Expr> },
+    Binary { op: BinaryOp, left: Box<Expr>, right: Box<Expr> },
+    // expr BETWEEN lower AND upper 或 expr NOT BETWEEN lower AND upper
+    Between { expr: Box<Expr>, lower: Box<Expr>, upper: Box<Expr>, negated: bool },
+    // 括号中的表达式列表，通常作为 IN 的右侧
+    List(Vec<Expr>),
+    // 函数调用
+    Func { name: String, args: Vec<Expr> },
+    // 可移植的函数，按 Flavor 渲染为对应的写法，见 func 模块
+    Call { func: Function, args: Vec<Expr> },
+    // CASE [operand] WHEN ... THEN ... [ELSE ...] END
+    Case {
+        operand: Option<Box<Expr>>,
+        whens: Vec<(Expr, Expr)>,
+        otherwise: Option<Box<Expr>>,
+    },
+    // CAST(expr AS ty)
+    Cast { expr: Box<Expr>, ty: String },
+    Subquery(Subquery),
+    // 从表函数中选出一列的子查询，(SELECT column FROM name(args) [AS alias])，通常作为 IN 的右侧
+    TableFunc {
+        column: String,
+        name: String,
+        args: Vec<Expr>,
+        alias: Option<String>,
+    },
+    // 窗口函数，expr OVER (...)
+    Over { expr: Box<Expr>, window: Window },
+    // 带过滤条件的聚合函数，expr FILTER (WHERE condition)
+    Filter { expr: Box<Expr>, condition: Box<Expr> },
+    // 函数语法中的子句，expr keyword arg，例如 GROUP_CONCAT(x SEPARATOR ',') 中的参数、
+    // LISTAGG(x, ',') WITHIN GROUP (ORDER BY x)，也用于拼接内部生成的子查询，不是运算符，两侧都不会加括号
+    Clause { expr: Box<Expr>, keyword: &'static str, arg: Box<Expr> },
+}
+
+impl Expr {
+    // 返回列名表达式
+    pub fn col(name: &str) -> Expr {
+        Expr::Column(name.to_string())
+    }
+
+    // 返回绑定为参数的值
+    pub fn val(value: impl ToSqlValue) -> Expr {
+        Expr::Value(value.to_sql_value())
+    }
+
+    // 返回原样输出的 SQL 片段
+    pub fn raw(sql: &str) -> Expr {
+        Expr::Raw(sql.to_string())
+    }
+
+    // 返回函数调用表达式
+    pub fn func<E: Into<Expr>>(name: &str, args: impl IntoIterator<Item = E>) -> Expr {
+        Expr::Func {
+            name: name.to_string(),
+            args: args.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    // 返回子查询表达式
+    pub fn subquery(builder: impl Builder + Send + Sync + 'static) -> Expr {
+        Expr::Subquery(Subquery(Arc::new(builder)))
+    }
+
+    // 返回 self = other
+    pub fn eq(self, other: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::Eq, other)
+    }
+
+    // 返回 self <> other
+    pub fn ne(self, other: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::Ne, other)
+    }
+
+    // 返回 self > other
+    pub fn gt(self, other: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::Gt, other)
+    }
+
+    // 返回 self >= other
+    pub fn ge(self, other: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::Ge, other)
+    }
+
+    // 返回 self < other
+    pub fn lt(self, other: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::Lt, other)
+    }
+
+    // 返回 self <= other
+    pub fn le(self, other: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::Le, other)
+    }
+
+    // 返回 self LIKE pattern
+    pub fn like(self, pattern: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::Like, pattern)
+    }
+
+    // 返回 self NOT LIKE pattern
+    pub fn not_like(self, pattern: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::NotLike, pattern)
+    }
+
+    // 返回 self IN (values)，values 为空时渲染为恒假的 0 = 1
+    // values 只有一个子查询时渲染为 self IN (subquery)
+    pub fn in_<E: Into<Expr>>(self, values: impl IntoIterator<Item = E>) -> Expr {
+        self.binary(BinaryOp::In, Expr::List(values.into_iter().map(Into::into).collect()))
+    }
+
+    // 返回 self NOT IN (values)，values 为空时渲染为恒真的 0 = 0
+    pub fn not_in<E: Into<Expr>>(self, values: impl IntoIterator<Item = E>) -> Expr {
+        self.binary(BinaryOp::NotIn, Expr::List(values.into_iter().map(Into::into).collect()))
+    }
+
+    // 返回 self BETWEEN lower AND upper
+    pub fn between(self, lower: impl Into<Expr>, upper: impl Into<Expr>) -> Expr {
+        Expr::Between {
+            expr: Box::new(self),
+            lower: Box::new(lower.into()),
+            upper: Box::new(upper.into()),
+            negated: false,
+        }
+    }
+
+    // 返回 self NOT BETWEEN lower AND upper
+    pub fn not_between(self, lower: impl Into<Expr>, upper: impl Into<Expr>) -> Expr {
+        Expr::Between {
+            expr: Box::new(self),
+            lower: Box::new(lower.into()),
+            upper: Box::new(upper.into()),
+            negated: true,
+        }
+    }
+
+    // 返回 self IS NULL
+    #[allow(clippy::wrong_self_convention)]
+    pub fn is_null(self) -> Expr {
+        self.unary(UnaryOp::IsNull)
+    }
+
+    // 返回 self IS NOT NULL
+    #[allow(clippy::wrong_self_convention)]
+    pub fn is_not_null(self) -> Expr {
+        self.unary(UnaryOp::IsNotNull)
+    }
+
+    // 返回 self AND other，也可以写成 self & other
+    pub fn and(self, other: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::And, other)
+    }
+
+    // 返回 self OR other，也可以写成 self | other
+    pub fn or(self, other: impl Into<Expr>) -> Expr {
+        self.binary(BinaryOp::Or, other)
+    }
+
+    // 返回 CAST(self AS ty)
+    pub fn cast(self, ty: &str) -> Expr {
+        Expr::Cast {
+            expr: Box::new(self),
+            ty: ty.to_string(),
+        }
+    }
+
+    // 返回 self OVER (window)
+    // Flavor 不支持窗口函数时，严格模式返回 UnsupportedFeature，宽松模式原样生成
+    pub fn over(self, window: Window) -> Expr {
+        Expr::Over {
+            expr: Box::new(self),
+            window,
+        }
+    }
+
+    // 返回 self FILTER (WHERE condition)
+    // Flavor 不支持 FILTER 时，严格模式返回 UnsupportedFeature，
+    // 宽松模式将聚合函数的第一个参数改写为 CASE WHEN condition THEN arg END，COUNT(*) 改写为 COUNT(CASE WHEN condition THEN 1 END)，
+    // 无法改写（例如不是函数调用）时返回 UnsupportedFeature
+    pub fn filter(self, condition: impl Into<Expr>) -> Expr {
+        Expr::Filter {
+            expr: Box::new(self),
+            condition: Box::new(condition.into()),
+        }
+    }
+
+    // 返回 self op other
+    pub fn binary(self, op: BinaryOp, other: impl Into<Expr>) -> Expr {
+        Expr::Binary {
+            op,
+            left: Box::new(self),
+            right: Box::new(other.into()),
+        }
+    }
+
+    // 返回 op self
+    pub fn unary(self, op: UnaryOp) -> Expr {
+        Expr::Unary { op, expr: Box::new(self) }
+    }
+
+    // 按先序遍历表达式的每个节点
+    pub fn walk(&self, f: &mut impl FnMut(&Expr)) {
+        f(self);
+
+        match self {
+            Expr::Column(_) | Expr::Value(_) | Expr::Raw(_) | Expr::Subquery(_) => {}
+            Expr::Unary { expr, .. } | Expr::Cast { expr, .. } => expr.walk(f),
+            Expr::Over { expr, window } => {
+                expr.walk(f);
+                window.partition_by.iter().chain(window.order_by.iter().map(|(e, _)| e)).for_each(|e| e.walk(f));
+            }
+            Expr::Filter { expr, condition } | Expr::Clause { expr, arg: condition, .. } => {
+                expr.walk(f);
+                condition.walk(f);
+            }
+            Expr::Binary { left, right, .. } => {
+                left.walk(f);
+                right.walk(f);
+            }
+            Expr::Between { expr, lower, upper, .. } => {
+                expr.walk(f);
+                lower.walk(f);
+                upper.walk(f);
+            }
+            Expr::List(exprs)
+            | Expr::Func { args: exprs, .. }
+            | Expr::Call { args: exprs, .. }
+            | Expr::TableFunc { args: exprs, .. } => exprs.iter().for_each(|e| e.walk(f)),
+            Expr::Case { operand, whens, otherwise } => {
+                if let Some(operand) = operand {
+                    operand.walk(f);
+                }
+
+                for (when, then) in whens {
+                    when.walk(f);
+                    then.walk(f);
+                }
+
+                if let Some(otherwise) = otherwise {
+                    otherwise.walk(f);
+                }
+            }
+        }
+    }
+
+    // 自底向上改写表达式，先改写子节点，再用 f 改写节点本身
+    pub fn transform(self, f: &mut impl FnMut(Expr) -> Expr) -> Expr {
+        let expr = match self {
+            Expr::Column(_) | Expr::Value(_) | Expr::Raw(_) | Expr::Subquery(_) => self,
+            Expr::Unary { op, expr } => Expr::Unary {
+                op,
+                expr: Box::new(expr.transform(f)),
+            },
+            Expr::Binary { op, left, right } => Expr::Binary {
+                op,
+                left: Box::new(left.transform(f)),
+                right: Box::new(right.transform(f)),
+            },
+            Expr::Between { expr, lower, upper, negated } => Expr::Between {
+                expr: Box::new(expr.transform(f)),
+                lower: Box::new(lower.transform(f)),
+                upper: Box::new(upper.transform(f)),
+                negated,
+            },
+            Expr::List(exprs) => Expr::List(exprs.into_iter().map(|e| e.transform(f)).collect()),
+            Expr::Func { name, args } => Expr::Func {
+                name,
+                args: args.into_iter().map(|e| e.transform(f)).collect(),
+            },
+            Expr::Call { func, args } => Expr::Call {
+                func,
+                args: args.into_iter().map(|e| e.transform(f)).collect(),
+            },
+            Expr::TableFunc { column, name, args, alias } => Expr::TableFunc {
+                column,
+                name,
+                args: args.into_iter().map(|e| e.transform(f)).collect(),
+                alias,
+            },
+            Expr::Case { operand, whens, otherwise } => Expr::Case {
+                operand: operand.map(|e| Box::new(e.transform(f))),
+                whens: whens
+                    .into_iter()
+                    .map(|(when, then)| (when.transform(f), then.transform(f)))
+                    .collect(),
+                otherwise: otherwise.map(|e| Box::new(e.transform(f))),
+            },
+            Expr::Cast { expr, ty } => Expr::Cast {
+                expr: Box::new(expr.transform(f)),
+                ty,
+            },
+            Expr::Over { expr, window } => Expr::Over {
+                expr: Box::new(expr.transform(f)),
+                window: Window {
+                    partition_by: window.partition_by.into_iter().map(|e| e.transform(f)).collect(),
+                    order_by: window.order_by.into_iter().map(|(e, order)| (e.transform(f), order)).collect(),
+                    ..window
+                },
+            },
+            Expr::Filter { expr, condition } => Expr::Filter {
+                expr: Box::new(expr.transform(f)),
+                condition: Box::new(condition.transform(f)),
+            },
+            Expr::Clause { expr, keyword, arg } => Expr::Clause {
+                expr: Box::new(expr.transform(f)),
+                keyword,
+                arg: Box::new(arg.transform(f)),
+            },
+        };
+
+        f(expr)
+    }
+
+    // 按 args 的 Flavor 渲染表达式，参数添加到 args 中，返回包含占位标记的字符串
+    // 需要加引号的列名不能安全地加引号时返回 InvalidIdentifier
+    pub fn render(&self, args: &mut Args) -> Result<String, Error> {
+        let mut buf = StringBuilder::new();
+        self.write_to(&mut buf, args)?;
+        Ok(buf.to_string().to_string())
+    }
+
+    pub(crate) fn write_to(&self, buf: &mut StringBuilder, args: &mut Args) -> Result<(), Error> {
+        match self {
+            Expr::Column(name) => buf.write_string(&escape(&column_name(&args.flavor, name)?)),
+            Expr::Value(value) => buf.write_string(&args.add(value.clone())),
+            Expr::Raw(sql) => buf.write_string(&escape(sql)),
+            Expr::Unary { op, expr } => match op {
+                UnaryOp::Not => {
+                    buf.write_string("NOT ");
+                    expr.write_operand(buf, args, PREC_NOT, false)?;
+                }
+                UnaryOp::Neg => {
+                    buf.write_rune('-');
+
+                    // 避免与操作数开头的 - 连成注释 --
+                    match expr.as_ref() {
+                        Expr::Column(_) | Expr::Value(_) | Expr::Func { .. } => expr.write_to(buf, args)?,
+                        _ => {
+                            buf.write_rune('(');
+                            expr.write_to(buf, args)?;
+                            buf.write_rune(')');
+                        }
+                    }
+                }
+                UnaryOp::IsNull | UnaryOp::IsNotNull => {
+                    expr.write_operand(buf, args, PREC_COMPARE, true)?;
+                    buf.write_string(if *op == UnaryOp::IsNull { " IS NULL" } else { " IS NOT NULL" });
+                }
+            },
+            Expr::Binary { op, left, right } => {
+                if let (BinaryOp::In | BinaryOp::NotIn, Expr::List(values)) = (op, right.as_ref()) {
+                    if values.is_empty() {
+                        buf.write_string(if *op == BinaryOp::In { "0 = 1" } else { "0 = 0" });
+                        return Ok(());
+                    }
+
+                    if let Some(rewritten) = in_list::rewrite(&args.flavor, args.pack_in_lists, *op, left, values) {
+                        return rewritten.write_to(buf, args);
+                    }
+                }
+
+                // 比较运算符不能连用，左侧同优先级的表达式也要加括号
+                let prec = op.precedence();
+                left.write_operand(buf, args, prec, prec == PREC_COMPARE)?;
+                buf.write_rune(' ');
+                buf.write_string(&escape(op.as_str()));
+                buf.write_rune(' ');
+                right.write_operand(buf, args, prec, !op.is_associative())?;
+            }
+            Expr::Between { expr, lower, upper, negated } => {
+                expr.write_operand(buf, args, PREC_COMPARE, true)?;
+                buf.write_string(if *negated { " NOT BETWEEN " } else { " BETWEEN " });
+                lower.write_operand(buf, args, PREC_COMPARE, true)?;
+                buf.write_string(" AND ");
+                upper.write_operand(buf, args, PREC_COMPARE, true)?;
+            }
+            Expr::List(exprs) => match exprs.as_slice() {
+                [query @ (Expr::Subquery(_) | Expr::TableFunc { .. })] => query.write_to(buf, args)?,
+                _ => {
+                    buf.write_rune('(');
+                    write_list(buf, args, exprs)?;
+                    buf.write_rune(')');
+                }
+            },
+            Expr::Func { name, args: params } => {
+                buf.write_string(&escape(name));
+                buf.write_rune('(');
+                write_list(buf, args, params)?;
+                buf.write_rune(')');
+            }
+            Expr::Call { func, args: params } => func.lower(&args.flavor, args.mode, params)?.write_to(buf, args)?,
+            // 没有 WHEN 分支的 CASE 不合法，直接使用 ELSE 的值
+            Expr::Case { whens, otherwise, .. } if whens.is_empty() => match otherwise {
+                Some(otherwise) => otherwise.write_operand(buf, args, PREC_ATOM, false)?,
+                None => buf.write_string("NULL"),
+            },
+            Expr::Case { operand, whens, otherwise } => {
+                buf.write_string("CASE");
+
+                if let Some(operand) = operand {
+                    buf.write_rune(' ');
+                    operand.write_to(buf, args)?;
+                }
+
+                for (when, then) in whens {
+                    buf.write_string(" WHEN ");
+                    when.write_to(buf, args)?;
+                    buf.write_string(" THEN ");
+                    then.write_to(buf, args)?;
+                }
+
+                if let Some(otherwise) = otherwise {
+                    buf.write_string(" ELSE ");
+                    otherwise.write_to(buf, args)?;
+                }
+
+                buf.write_string(" END");
+            }
+            Expr::Cast { expr, ty } => {
+                buf.write_string("CAST(");
+                expr.write_to(buf, args)?;
+                buf.write_string(" AS ");
+                buf.write_string(&escape(ty));
+                buf.write_rune(')');
+            }
+            Expr::Subquery(Subquery(builder)) => {
+                buf.write_rune('(');
+                buf.write_string(&args.add_builder(builder.clone()));
+                buf.write_rune(')');
+            }
+            Expr::TableFunc { column, name, args: params, alias } => {
+                buf.write_string("(SELECT ");
+                buf.write_string(&escape(column));
+                buf.write_string(" FROM ");
+                buf.write_string(&escape(name));
+                buf.write_rune('(');
+                write_list(buf, args, params)?;
+                buf.write_rune(')');
+
+                if let Some(alias) = alias {
+                    buf.write_string(" AS ");
+                    buf.write_string(&escape(alias));
+                }
+
+                buf.write_rune(')');
+            }
+            Expr::Over { expr, window } => {
+                args.flavor.check_feature(Feature::WindowFunction, args.mode)?;
+                expr.write_to(buf, args)?;
+                buf.write_string(" OVER ");
+
+                match &window.name {
+                    Some(name) if window.is_reference() => buf.write_string(&escape(name)),
+                    _ => {
+                        let window = window.render(args)?;
+                        buf.write_rune('(');
+                        buf.write_string(&window);
+                        buf.write_rune(')');
+                    }
+                }
+            }
+            Expr::Filter { expr, condition } => {
+                if args.flavor.check_feature(Feature::AggregateFilter, args.mode)? {
+                    expr.write_to(buf, args)?;
+                    buf.write_string(" FILTER (WHERE ");
+                    condition.write_to(buf, args)?;
+                    buf.write_rune(')');
+                } else {
+                    emulate_filter(&args.flavor, args.mode, expr, condition)?.write_to(buf, args)?;
+                }
+            }
+            Expr::Clause { expr, keyword, arg } => {
+                expr.write_to(buf, args)?;
+                buf.write_rune(' ');
+                buf.write_string(keyword);
+                buf.write_rune(' ');
+                arg.write_to(buf, args)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // 作为运算符的操作数写入，优先级低于 prec 时加括号，strict 为 true 时同优先级也加括号
+    fn write_operand(&self, buf: &mut StringBuilder, args: &mut Args, prec: u8, strict: bool) -> Result<(), Error> {
+        // 可移植函数的优先级取决于渲染后的写法
+        if let Expr::Call { func, args: params } = self {
+            return func.lower(&args.flavor, args.mode, params)?.write_operand(buf, args, prec, strict);
+        }
+
+        let own = self.precedence();
+
+        if own < prec || (strict && own == prec) {
+            buf.write_rune('(');
+            self.write_to(buf, args)?;
+            buf.write_rune(')');
+            return Ok(());
+        }
+
+        self.write_to(buf, args)
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Binary { op, .. } => op.precedence(),
+            Expr::Unary { op: UnaryOp::Not, .. } => PREC_NOT,
+            Expr::Unary { op: UnaryOp::Neg, .. } => PREC_NEG,
+            Expr::Unary { .. } | Expr::Between { .. } => PREC_COMPARE,
+            _ => PREC_ATOM,
+        }
+    }
+}
+
+// 用 CASE 模拟聚合函数的 FILTER 子句，只改写第一个参数
+// 可移植函数先按 Flavor 展开，无法改写时返回 UnsupportedFeature
+fn emulate_filter(flavor: &Flavor, mode: BuildMode, expr: &Expr, condition: &Expr) -> Result<Expr, Error> {
+    let lowered;
+    let expr = match expr {
+        Expr::Call { func, args } => {
+            lowered = func.lower(flavor, mode, args)?;
+            &lowered
+        }
+        _ => expr,
+    };
+    let unsupported = || Error::UnsupportedFeature {
+        flavor: flavor.clone(),
+        feature: Feature::AggregateFilter,
+    };
+    let Expr::Func { name, args } = expr else {
+        return Err(unsupported());
+    };
+    let (first, rest) = args.split_first().ok_or_else(unsupported)?;
+
+    let then = match first {
+        Expr::Column(name) if name == "*" => Expr::Raw("1".to_string()),
+        _ => first.clone(),
+    };
+    let first = Expr::Case {
+        operand: None,
+        whens: vec![(condition.clone(), then)],
+        otherwise: None,
+    };
+
+    Ok(Expr::Func {
+        name: name.clone(),
+        args: std::iter::once(first).chain(rest.iter().cloned()).collect(),
+    })
+}
+
+// 用逗号连接多个表达式
+pub(crate) fn write_list(buf: &mut StringBuilder, args: &mut Args, exprs: &[Expr]) -> Result<(), Error> {
+    for (i, expr) in exprs.iter().enumerate() {
+        if i > 0 {
+            buf.write_string(", ");
+        }
+
+        expr.write_to(buf, args)?;
+    }
+
+    Ok(())
+}
+
+// 按需为列名的每一段加引号
+fn column_name(flavor: &Flavor, name: &str) -> Result<String, Error> {
+    let parts = name
+        .split('.')
+        .map(|part| {
+            if part == "*" || !flavor.needs_quote(part) {
+                return Ok(part.to_string());
+            }
+
+            flavor.check_identifier(part)?;
+            Ok(flavor.quote(part))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+
+    Ok(parts.join("."))
+}
+
+impl<T: ToSqlValue> From<T> for Expr {
+    fn from(value: T) -> Self {
+        Expr::Value(value.to_sql_value())
+    }
+}
+
+impl<T> From<Column<T>> for Expr {
+    fn from(column: Column<T>) -> Self {
+        Expr::Column(column.qualified())
+    }
+}
+
+impl From<Condition> for Expr {
+    fn from(condition: Condition) -> Self {
+        match condition {
+            Condition::Compare { field, op, value } => {
+                let op = match op {
+                    "=" => BinaryOp::Eq,
+                    "<>" => BinaryOp::Ne,
+                    ">" => BinaryOp::Gt,
+                    ">=" => BinaryOp::Ge,
+                    "<" => BinaryOp::Lt,
+                    "<=" => BinaryOp::Le,
+                    "LIKE" => BinaryOp::Like,
+                    "NOT LIKE" => BinaryOp::NotLike,
+                    op => BinaryOp::Other(op),
+                };
+
+                Expr::Column(field).binary(op, Expr::Value(value))
+            }
+            Condition::IsNull { field, negated: false } => Expr::Column(field).is_null(),
+            Condition::IsNull { field, negated: true } => Expr::Column(field).is_not_null(),
+            Condition::In { field, values, negated: false } => Expr::Column(field).in_(values),
+            Condition::In { field, values, negated: true } => Expr::Column(field).not_in(values),
+            Condition::Between { field, lower, upper, negated: false } => Expr::Column(field).between(lower, upper),
+            Condition::Between { field, lower, upper, negated: true } => Expr::Column(field).not_between(lower, upper),
+            Condition::And(conditions) => join(conditions, BinaryOp::And),
+            Condition::Or(conditions) => join(conditions, BinaryOp::Or),
+            Condition::Not(condition) => !Expr::from(*condition),
+        }
+    }
+}
+
+// 用 AND 或 OR 连接多个条件
+// 没有条件时与空的 IN 一样返回恒定的条件：AND 为恒真的 0 = 0，OR 为恒假的 0 = 1
+fn join(conditions: Vec<Condition>, op: BinaryOp) -> Expr {
+    conditions
+        .into_iter()
+        .map(Expr::from)
+        .reduce(|left, right| left.binary(op, right))
+        .unwrap_or_else(|| Expr::raw(if op == BinaryOp::And { "0 = 0" } else { "0 = 1" }))
+}
+
+// 实现 +、-、*、/、%、& 和 | 运算符，& 和 | 分别对应 AND 和 OR
+macro_rules! impl_binary_ops {
+    ($($trait:ident :: $method:ident => $op:ident),* $(,)?) => {
+        $(
+            impl<R: Into<Expr>> $trait<R> for Expr {
+                type Output = Expr;
+
+                fn $method(self, rhs: R) -> Expr {
+                    self.binary(BinaryOp::$op, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_binary_ops! {
+    Add::add => Add,
+    Sub::sub => Sub,
+    Mul::mul => Mul,
+    Div::div => Div,
+    Rem::rem => Mod,
+    BitAnd::bitand => And,
+    BitOr::bitor => Or,
+}
+
+impl Not for Expr {
+    type Output = Expr;
+
+    fn not(self) -> Expr {
+        self.unary(UnaryOp::Not)
+    }
+}
+
+impl Neg for Expr {
+    type Output = Expr;
+
+    fn neg(self) -> Expr {
+        self.unary(UnaryOp::Neg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::RawBuilder;
+
+    #[test]
+    fn test_expr() {
+        let expr = (Expr::col("price") * 2 + 1).gt(Expr::col("limit"))
+            & (Expr::col("users.name").like("a%") | !Expr::col("deleted_at").is_null())
+            & Expr::col("kind").not_in(Vec::<i32>::new());
+
+        let mut args = Args::new();
+        let format = args.add_expr(expr.clone());
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            r#"price * $1 + $2 > "limit" AND (users.name LIKE $3 OR NOT deleted_at IS NULL) AND 0 = 0"#
+        );
+        assert_eq!(values, vec![Value::Int(2), Value::Int(1), Value::String("a%".to_string())]);
+
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::MySQL, Vec::new()).unwrap();
+        assert_eq!(sql, "price * ? + ? > `limit` AND (users.name LIKE ? OR NOT deleted_at IS NULL) AND 0 = 0");
+
+        let mut columns = Vec::new();
+        expr.walk(&mut |e| {
+            if let Expr::Column(name) = e {
+                columns.push(name.clone());
+            }
+        });
+        assert_eq!(columns, vec!["price", "limit", "users.name", "deleted_at", "kind"]);
+
+        let rewritten = (Expr::col("a") - (Expr::col("b") - 1)).transform(&mut |e| match e {
+            Expr::Column(name) => Expr::Column(format!("t.{}", name)),
+            e => e,
+        });
+        let mut args = Args::new();
+        assert_eq!(rewritten.render(&mut args).unwrap(), "t.a - (t.b - $0)");
+
+        // 空的 AND 恒真，空的 OR 恒假
+        let expr = Expr::from(Condition::And(Vec::new())).and(Expr::from(Condition::Or(Vec::new())));
+        assert_eq!(expr.render(&mut Args::new()).unwrap(), "0 = 0 AND 0 = 1");
+    }
+
+    #[test]
+    fn test_expr_subquery() {
+        let subquery = Expr::subquery(RawBuilder::new(|args| {
+            format!("SELECT user_id FROM orders WHERE total > {}", args.add(100))
+        }));
+
+        let expr = Expr::col("age")
+            .between(18, 30)
+            .and(Expr::col("id").in_([subquery]))
+            .and(Expr::func("COALESCE", [Expr::col("nick"), Expr::val("")]).cast("TEXT").ne(Expr::col("name")));
+
+        let mut args = Args::new();
+        let format = format!("SELECT * FROM users WHERE {} AND id <> {}", args.add_expr(expr), args.add(0));
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT * FROM users WHERE age BETWEEN $1 AND $2 AND id IN (SELECT user_id FROM orders WHERE total > $3) \
+             AND CAST(COALESCE(nick, $4) AS TEXT) <> name AND id <> $5"
+        );
+        assert_eq!(values.len(), 5);
+        assert_eq!(values[2], Value::Int(100));
+    }
+}
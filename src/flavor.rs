@@ -1,18 +1,68 @@
+use crate::args::{escape, Args};
+use crate::create_table::CreateTableBuilder;
+use crate::builder::{BuildMode, Builder};
+use crate::dialect::{self, Capabilities, Dialect, Feature, IdentifierCase, KeyModel, Limits, UpsertSyntax};
 use crate::error::Error;
-use crate::interpolate::{
-    clickhouse_interpolate, cql_interpolate, informix_interpolate, mysql_interpolate,
-    oracle_interpolate, postgresql_interpolate, presto_interpolate, sqlite_interpolate,
-    sqlserver_interpolate,
-};
+use crate::expr::Expr;
+use crate::interpolate;
+use crate::keywords;
+use crate::stringbuilder::StringBuilder;
+use crate::value::Value;
+use std::cell::RefCell;
 use std::fmt;
+use std::str::FromStr;
+use std::sync::RwLock;
 
-// 定义错误类型
+// 进程级的默认 Flavor，未指定 Flavor 的构造器都会使用它
+static DEFAULT_FLAVOR: RwLock<Flavor> = RwLock::new(Flavor::MySQL);
+
+thread_local! {
+    // 当前线程临时覆盖的默认 Flavor
+    static SCOPED_FLAVOR: RefCell<Option<Flavor>> = const { RefCell::new(None) };
+}
+
+// 设置进程级的默认 Flavor，返回原来的值
+pub fn set_default_flavor(flavor: Flavor) -> Flavor {
+    let mut current = DEFAULT_FLAVOR.write().unwrap_or_else(|e| e.into_inner());
+    std::mem::replace(&mut *current, flavor)
+}
+
+// 返回当前生效的默认 Flavor
+// 优先使用 with_flavor 在当前线程设置的值，其次是进程级的默认值
+pub fn default_flavor() -> Flavor {
+    if let Some(flavor) = SCOPED_FLAVOR.with(|scoped| scoped.borrow().clone()) {
+        return flavor;
+    }
+
+    DEFAULT_FLAVOR.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+// 在闭包执行期间将当前线程的默认 Flavor 替换为指定值
+// 覆盖只对当前线程有效，可以嵌套使用，闭包返回或 panic 后都会恢复原值
+// 在会跨线程调度的异步任务中，应在任务内部调用 with_flavor 或显式设置构造器的 Flavor
+pub fn with_flavor<R>(flavor: Flavor, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<Flavor>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            SCOPED_FLAVOR.with(|scoped| *scoped.borrow_mut() = previous);
+        }
+    }
+
+    let previous = SCOPED_FLAVOR.with(|scoped| scoped.borrow_mut().replace(flavor));
+    let _restore = Restore(previous);
+    f()
+}
 
 // 定义 Flavor 枚举
-#[derive(Debug, Clone, PartialEq, Eq)]
+// 变体名称沿用数据库的惯用写法，例如 CQL
+#[derive(Clone)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum Flavor {
-    InvalidFlavor,
     MySQL,
+    // MySQL 8.0 之前的版本，SQL 写法与 MySQL 相同，不支持窗口函数
+    MySQL57,
     PostgreSQL,
     SQLite,
     SQLServer,
@@ -21,41 +71,121 @@ pub enum Flavor {
     Presto,
     Oracle,
     Informix,
+    DuckDB,
+    Snowflake,
+    BigQuery,
+    MariaDB,
+    Doris,
+    StarRocks,
+    // 自定义方言
+    Custom(&'static dyn Dialect),
 }
 
 impl Flavor {
-    // 返回 Flavor 的字符串表示
-    pub fn to_string(&self) -> &str {
-        match self {
-            Flavor::MySQL => "MySQL",
-            Flavor::PostgreSQL => "PostgreSQL",
-            Flavor::SQLite => "SQLite",
-            Flavor::SQLServer => "SQLServer",
-            Flavor::CQL => "CQL",
-            Flavor::ClickHouse => "ClickHouse",
-            Flavor::Presto => "Presto",
-            Flavor::Oracle => "Oracle",
-            Flavor::Informix => "Informix",
-            _ => "<invalid>",
+    // 返回 Flavor 的名称
+    pub fn name(&self) -> &str {
+        self.dialect().name()
+    }
+
+    // 根据连接 URL 的协议识别 Flavor
+    // 支持 postgres://、mysql://、sqlite: 等形式，也支持 jdbc: 前缀和 postgresql+driver:// 这类协议
+    pub fn from_url(url: &str) -> Result<Flavor, Error> {
+        let url = url.strip_prefix("jdbc:").unwrap_or(url);
+        let scheme = match url.find(':') {
+            Some(pos) => &url[..pos],
+            None => return Err(Error::InvalidFlavor(url.to_string())),
+        };
+        let scheme = scheme.split('+').next().unwrap_or(scheme);
+
+        match scheme.to_ascii_lowercase().as_str() {
+            "tcp" | "http" | "https" | "file" => Err(Error::InvalidFlavor(scheme.to_string())),
+            "sqlserver" | "mssql" | "tds" => Ok(Flavor::SQLServer),
+            "cassandra" | "scylla" => Ok(Flavor::CQL),
+            "trino" => Ok(Flavor::Presto),
+            _ => scheme.parse(),
         }
     }
 
-    // 对于不同的数据库类型，返回其相应的插值方法
-    pub fn interpolate(&self, sql: &str, args: &[Box<dyn std::fmt::Debug>]) -> Result<String, Err> {
+    // 使用自定义方言创建 Flavor
+    // 与内置方言一样，自定义方言通常定义为 static，返回的 Flavor 可以随意复制
+    pub fn custom(dialect: &'static dyn Dialect) -> Flavor {
+        Flavor::Custom(dialect)
+    }
+
+    // 返回 Flavor 对应的方言
+    pub fn dialect(&self) -> &'static dyn Dialect {
         match self {
-            Flavor::MySQL => mysql_interpolate(sql, args),
-            Flavor::PostgreSQL => postgresql_interpolate(sql, args),
-            Flavor::SQLite => sqlite_interpolate(sql, args),
-            Flavor::SQLServer => sqlserver_interpolate(sql, args),
-            Flavor::CQL => cql_interpolate(sql, args),
-            Flavor::ClickHouse => clickhouse_interpolate(sql, args),
-            Flavor::Presto => presto_interpolate(sql, args),
-            Flavor::Oracle => oracle_interpolate(sql, args),
-            Flavor::Informix => informix_interpolate(sql, args),
-            _ => Err(Error::NotImplemented),
+            Flavor::MySQL => &dialect::MYSQL,
+            Flavor::MySQL57 => &dialect::MYSQL_57,
+            Flavor::PostgreSQL => &dialect::POSTGRESQL,
+            Flavor::SQLite => &dialect::SQLITE,
+            Flavor::SQLServer => &dialect::SQLSERVER,
+            Flavor::CQL => &dialect::CQL,
+            Flavor::ClickHouse => &dialect::CLICKHOUSE,
+            Flavor::Presto => &dialect::PRESTO,
+            Flavor::Oracle => &dialect::ORACLE,
+            Flavor::Informix => &dialect::INFORMIX,
+            Flavor::DuckDB => &dialect::DUCKDB,
+            Flavor::Snowflake => &dialect::SNOWFLAKE,
+            Flavor::BigQuery => &dialect::BIGQUERY,
+            Flavor::MariaDB => &dialect::MARIADB,
+            Flavor::Doris => &dialect::DORIS,
+            Flavor::StarRocks => &dialect::STARROCKS,
+            Flavor::Custom(dialect) => *dialect,
         }
     }
 
+    // 返回插入或更新的语法
+    pub fn upsert_syntax(&self) -> UpsertSyntax {
+        self.dialect().upsert_syntax()
+    }
+
+    // 返回建表时声明主键的方式
+    pub fn key_model(&self) -> KeyModel {
+        self.dialect().key_model()
+    }
+
+    // 判断是否支持指定的特性
+    pub fn supports(&self, feature: Feature) -> bool {
+        self.dialect().supports(feature)
+    }
+
+    // 检查构造器用到的特性，支持时返回 true
+    // 不支持时，严格模式返回 UnsupportedFeature，宽松模式返回 false，由构造器决定模拟还是原样生成
+    pub fn check_feature(&self, feature: Feature, mode: BuildMode) -> Result<bool, Error> {
+        if self.supports(feature) {
+            return Ok(true);
+        }
+
+        match mode {
+            BuildMode::Strict => Err(Error::UnsupportedFeature {
+                flavor: self.clone(),
+                feature,
+            }),
+            BuildMode::Lenient => Ok(false),
+        }
+    }
+
+    // 返回方言的词法和字面量能力
+    pub fn capabilities(&self) -> Capabilities {
+        self.dialect().capabilities()
+    }
+
+    // 返回方言对参数数量的限制
+    pub fn limits(&self) -> Limits {
+        self.dialect().limits()
+    }
+
+    // 对于不同的数据库类型，使用其方言将参数内联到 SQL 中
+    pub fn interpolate(&self, sql: &str, args: &[Value]) -> Result<String, Error> {
+        interpolate::interpolate(self.dialect(), sql, args)
+    }
+
+    // 返回第 n 个参数（从 1 开始）在该方言中的占位符
+    pub fn placeholder(&self, n: usize) -> String {
+        self.dialect().placeholder(n)
+    }
+
     // 创建 CREATE TABLE 构造器
     pub fn new_create_table_builder(&self) -> CreateTableBuilder {
         let mut builder = CreateTableBuilder::new();
@@ -63,13 +193,6 @@ impl Flavor {
         builder
     }
 
-    // 创建 DELETE 构造器
-    pub fn new_delete_builder(&self) -> DeleteBuilder {
-        let mut builder = DeleteBuilder::new();
-        builder.set_flavor(self.clone());
-        builder
-    }
-
     // 创建 INSERT 构造器
     pub fn new_insert_builder(&self) -> InsertBuilder {
         let mut builder = InsertBuilder::new();
@@ -77,71 +200,91 @@ impl Flavor {
         builder
     }
 
-    // 创建 SELECT 构造器
-    pub fn new_select_builder(&self) -> SelectBuilder {
-        let mut builder = SelectBuilder::new();
-        builder.set_flavor(self.clone());
-        builder
+    // 为数据库名称添加引号
+    pub fn quote(&self, name: &str) -> String {
+        self.dialect().quote(name)
     }
 
-    // 创建 UPDATE 构造器
-    pub fn new_update_builder(&self) -> UpdateBuilder {
-        let mut builder = UpdateBuilder::new();
-        builder.set_flavor(self.clone());
-        builder
+    // 返回该方言的保留关键字列表
+    pub fn reserved_keywords(&self) -> &'static [&'static str] {
+        self.dialect().reserved_keywords()
     }
 
-    // 创建 UNION 构造器
-    pub fn new_union_builder(&self) -> UnionBuilder {
-        let mut builder = UnionBuilder::new();
-        builder.set_flavor(self.clone());
-        builder
+    // 判断名称是否为该方言的保留关键字（不区分大小写）
+    pub fn is_reserved_keyword(&self, name: &str) -> bool {
+        let upper = name.to_ascii_uppercase();
+        keywords::COMMON.contains(&upper.as_str())
+            || self.reserved_keywords().contains(&upper.as_str())
     }
 
-    // 创建 CTE 构造器
-    pub fn new_cte_builder(&self) -> CTEBuilder {
-        let mut builder = CTEBuilder::new();
-        builder.set_flavor(self.clone());
-        builder
+    // 判断单个标识符是否必须加引号才能原样使用
+    // 关键字、以数字开头、包含特殊字符，以及会被数据库折叠大小写的名称都需要加引号
+    pub fn needs_quote(&self, name: &str) -> bool {
+        if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+            return true;
+        }
+
+        if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return true;
+        }
+
+        let has_upper = name.chars().any(|c| c.is_ascii_uppercase());
+        let has_lower = name.chars().any(|c| c.is_ascii_lowercase());
+
+        let folded = match self.dialect().identifier_case() {
+            // 折叠为小写时，任何大写字母都会改变含义
+            IdentifierCase::Lower => has_upper,
+            // 折叠为大写时，只有大小写混合的名称才会改变含义
+            IdentifierCase::Upper => has_upper && has_lower,
+            IdentifierCase::Preserve => false,
+        };
+
+        folded || self.is_reserved_keyword(name)
     }
 
-    // 创建 CTE 查询构造器
-    pub fn new_cte_query_builder(&self) -> CTEQueryBuilder {
-        let mut builder = CTEQueryBuilder::new();
-        builder.set_flavor(self.clone());
-        builder
+    // 仅在必要时为名称加引号
+    // 形如 schema.table 的限定名称会逐段处理
+    pub fn quote_if_needed(&self, name: &str) -> String {
+        name.split('.')
+            .map(|part| {
+                if self.needs_quote(part) {
+                    self.quote(part)
+                } else {
+                    part.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(".")
     }
 
-    // 为数据库名称添加引号
-    pub fn quote(&self, name: &str) -> String {
-        match self {
-            Flavor::MySQL | Flavor::ClickHouse => format!("`{}`", name),
-            Flavor::PostgreSQL
-            | Flavor::SQLServer
-            | Flavor::SQLite
-            | Flavor::Presto
-            | Flavor::Oracle
-            | Flavor::Informix => format!("\"{}\"", name),
-            Flavor::CQL => format!("'{}'", name),
-            _ => name.to_string(),
+    // 检查名称能否安全地加引号
+    // 形如 schema.table 的限定名称中不能有空的部分，也不能包含方言的引号字符
+    pub fn check_identifier(&self, name: &str) -> Result<(), Error> {
+        let quotes = self.quote("");
+        let invalid = name
+            .split('.')
+            .any(|part| part.is_empty() || part.contains(|c| c == '\0' || quotes.contains(c)));
+
+        if invalid {
+            return Err(Error::InvalidIdentifier { name: name.to_string() });
         }
+
+        Ok(())
     }
 
     // 为 INSERT IGNORE 语句准备构造器
+    // 没有对应写法的方言使用 ON CONFLICT DO NOTHING，由构造器在编译时检查 Flavor 是否支持
     pub fn prepare_insert_ignore(&self, table: &str, ib: &mut InsertBuilder) {
         match self {
-            Flavor::MySQL | Flavor::Oracle => {
+            Flavor::MySQL | Flavor::MySQL57 | Flavor::MariaDB => {
                 ib.set_verb("INSERT IGNORE");
             }
-            Flavor::PostgreSQL => {
-                ib.set_verb("INSERT");
-                ib.add_sql("ON CONFLICT DO NOTHING");
-            }
-            Flavor::SQLite => {
+            Flavor::SQLite | Flavor::DuckDB => {
                 ib.set_verb("INSERT OR IGNORE");
             }
             _ => {
                 ib.set_verb("INSERT");
+                ib.on_conflict_do_nothing();
             }
         }
 
@@ -150,28 +293,100 @@ impl Flavor {
     }
 }
 
-// 假设我们有一个创建表的构造器（这个构造器是一个简单的示例）
-pub struct CreateTableBuilder {
-    flavor: Flavor,
+impl fmt::Debug for Flavor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Flavor::Custom(dialect) => write!(f, "Custom({})", dialect.name()),
+            _ => f.write_str(self.name()),
+        }
+    }
+}
+
+impl Default for Flavor {
+    fn default() -> Self {
+        default_flavor()
+    }
 }
 
-impl CreateTableBuilder {
-    pub fn new() -> Self {
-        CreateTableBuilder {
-            flavor: Flavor::InvalidFlavor,
+impl fmt::Display for Flavor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+// 从名称解析 Flavor，不区分大小写并支持常见别名
+impl FromStr for Flavor {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "mysql" => Ok(Flavor::MySQL),
+            "mysql57" | "mysql5.7" => Ok(Flavor::MySQL57),
+            "postgresql" | "postgres" | "pg" | "pgsql" => Ok(Flavor::PostgreSQL),
+            "sqlite" | "sqlite3" => Ok(Flavor::SQLite),
+            "sqlserver" | "sql_server" | "mssql" => Ok(Flavor::SQLServer),
+            "cql" | "cassandra" => Ok(Flavor::CQL),
+            "clickhouse" | "ch" => Ok(Flavor::ClickHouse),
+            "presto" | "trino" => Ok(Flavor::Presto),
+            "oracle" => Ok(Flavor::Oracle),
+            "informix" => Ok(Flavor::Informix),
+            "duckdb" => Ok(Flavor::DuckDB),
+            "snowflake" => Ok(Flavor::Snowflake),
+            "bigquery" | "bq" => Ok(Flavor::BigQuery),
+            "mariadb" => Ok(Flavor::MariaDB),
+            "doris" => Ok(Flavor::Doris),
+            "starrocks" => Ok(Flavor::StarRocks),
+            _ => Err(Error::InvalidFlavor(s.to_string())),
         }
     }
+}
+
+// 序列化为名称，自定义方言也使用其名称
+#[cfg(feature = "serde")]
+impl serde::Serialize for Flavor {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
 
-    pub fn set_flavor(&mut self, flavor: Flavor) {
-        self.flavor = flavor;
+// 反序列化只支持内置方言的名称和别名
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Flavor {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
     }
 }
 
+// 内置方言按变体比较，自定义方言按名称比较
+impl PartialEq for Flavor {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Flavor::Custom(a), Flavor::Custom(b)) => a.name() == b.name(),
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+impl Eq for Flavor {}
+
 // 示例的插入构造器
+// 只支持 INSERT INTO table (cols) VALUES (...)，以及可以按 Flavor 检查的 ON CONFLICT DO NOTHING 和 RETURNING
 pub struct InsertBuilder {
     verb: String,
     table: String,
+    cols: Vec<String>,
+    values: Vec<Vec<String>>,
+    on_conflict_do_nothing: bool,
+    returning: Vec<String>,
     marker: Option<String>,
+    args: Args,
+}
+
+impl Default for InsertBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl InsertBuilder {
@@ -179,16 +394,57 @@ impl InsertBuilder {
         InsertBuilder {
             verb: String::from("INSERT"),
             table: String::new(),
+            cols: Vec::new(),
+            values: Vec::new(),
+            on_conflict_do_nothing: false,
+            returning: Vec::new(),
             marker: None,
+            args: Args::new(),
         }
     }
 
+    pub fn set_flavor(&mut self, flavor: Flavor) -> Flavor {
+        std::mem::replace(&mut self.args.flavor, flavor)
+    }
+
+    // 设置遇到 Flavor 不支持的特性时的处理方式，返回原来的值
+    pub fn set_build_mode(&mut self, mode: BuildMode) -> BuildMode {
+        std::mem::replace(&mut self.args.mode, mode)
+    }
+
     pub fn set_verb(&mut self, verb: &str) {
         self.verb = verb.to_string();
     }
 
     pub fn set_table(&mut self, table: &str) {
-        self.table = table.to_string();
+        self.table = escape(table);
+    }
+
+    // 设置插入的列
+    pub fn cols(&mut self, cols: &[&str]) -> &mut Self {
+        self.cols = cols.iter().map(|c| escape(c)).collect();
+        self
+    }
+
+    // 添加一行值，值绑定为参数，表达式（例如 CASE）在编译时按 Flavor 渲染
+    pub fn values<E: Into<Expr>>(&mut self, values: impl IntoIterator<Item = E>) -> &mut Self {
+        let row = values.into_iter().map(|v| self.args.add_expr(v)).collect();
+        self.values.push(row);
+        self
+    }
+
+    // 添加 ON CONFLICT DO NOTHING
+    // Flavor 不支持时，严格模式返回 UnsupportedFeature，宽松模式下 MySQL 和 MariaDB 改写为 INSERT IGNORE，其他方言省略
+    pub fn on_conflict_do_nothing(&mut self) -> &mut Self {
+        self.on_conflict_do_nothing = true;
+        self
+    }
+
+    // 添加 RETURNING 子句
+    // Flavor 不支持时，严格模式返回 UnsupportedFeature，宽松模式下 SQL Server 改写为 OUTPUT INSERTED.col，其他方言省略
+    pub fn returning(&mut self, cols: &[&str]) -> &mut Self {
+        self.returning = cols.iter().map(|c| escape(c)).collect();
+        self
     }
 
     pub fn add_sql(&mut self, sql: &str) {
@@ -200,16 +456,123 @@ impl InsertBuilder {
     }
 }
 
+impl Builder for InsertBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: Vec<Value>) -> Result<(String, Vec<Value>), Error> {
+        let mode = self.args.mode;
+        let on_conflict_supported = !self.on_conflict_do_nothing || flavor.check_feature(Feature::OnConflict, mode)?;
+        let returning_supported = self.returning.is_empty() || flavor.check_feature(Feature::Returning, mode)?;
+
+        let mut verb = self.verb.as_str();
+        let mut on_conflict = self.on_conflict_do_nothing;
+
+        let mysql = matches!(flavor, Flavor::MySQL | Flavor::MySQL57 | Flavor::MariaDB);
+
+        if !on_conflict_supported {
+            if verb == "INSERT" && mysql {
+                verb = "INSERT IGNORE";
+            }
+
+            on_conflict = false;
+        }
+
+        let output = !returning_supported && matches!(flavor, Flavor::SQLServer);
+        let returning = returning_supported && !self.returning.is_empty();
+        let mut buf = StringBuilder::new();
+        buf.write_leading_string(verb);
+        buf.write_leading_string("INTO");
+        buf.write_leading_string(&self.table);
+
+        if !self.cols.is_empty() {
+            buf.write_leading_string("(");
+            buf.write_strings(&self.cols, ", ");
+            buf.write_rune(')');
+        }
+
+        if output {
+            let cols: Vec<String> = self.returning.iter().map(|c| format!("INSERTED.{}", c)).collect();
+            buf.write_leading_string("OUTPUT");
+            buf.write_leading_string(&cols.join(", "));
+        }
+
+        if !self.values.is_empty() {
+            let rows: Vec<String> = self.values.iter().map(|row| format!("({})", row.join(", "))).collect();
+            buf.write_leading_string("VALUES");
+            buf.write_leading_string(&rows.join(", "));
+        }
+
+        if on_conflict {
+            buf.write_leading_string("ON CONFLICT DO NOTHING");
+        }
+
+        if let Some(sql) = &self.marker {
+            buf.write_leading_string(sql);
+        }
+
+        if returning {
+            buf.write_leading_string("RETURNING");
+            buf.write_leading_string(&self.returning.join(", "));
+        }
+
+        self.args.compile_with_flavor(buf.to_string(), flavor, initial_arg)
+    }
+
+    fn flavor(&self) -> Flavor {
+        self.args.flavor.clone()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::dialect::PlaceholderStyle;
+    use crate::interpolate::debug_sql;
+    use crate::value::ToSqlValue;
 
     #[test]
     fn test_flavor_to_string() {
         assert_eq!(Flavor::MySQL.to_string(), "MySQL");
+        assert_eq!(Flavor::MySQL57.to_string(), "MySQL57");
         assert_eq!(Flavor::PostgreSQL.to_string(), "PostgreSQL");
         assert_eq!(Flavor::SQLite.to_string(), "SQLite");
-        assert_eq!(Flavor::InvalidFlavor.to_string(), "<invalid>");
+        assert_eq!(format!("{}", Flavor::DuckDB), "DuckDB");
+    }
+
+    #[test]
+    fn test_flavor_from_str() {
+        assert_eq!("mysql".parse::<Flavor>().unwrap(), Flavor::MySQL);
+        assert_eq!("PG".parse::<Flavor>().unwrap(), Flavor::PostgreSQL);
+        assert_eq!("Postgres".parse::<Flavor>().unwrap(), Flavor::PostgreSQL);
+        assert_eq!("mssql".parse::<Flavor>().unwrap(), Flavor::SQLServer);
+        assert_eq!("sqlite3".parse::<Flavor>().unwrap(), Flavor::SQLite);
+        assert!(matches!("nosql".parse::<Flavor>(), Err(Error::InvalidFlavor(_))));
+    }
+
+    #[test]
+    fn test_flavor_from_url() {
+        assert_eq!(Flavor::from_url("postgres://u:p@localhost/db").unwrap(), Flavor::PostgreSQL);
+        assert_eq!(Flavor::from_url("postgresql+psycopg2://localhost/db").unwrap(), Flavor::PostgreSQL);
+        assert_eq!(Flavor::from_url("mysql://root@127.0.0.1:3306/app").unwrap(), Flavor::MySQL);
+        assert_eq!(Flavor::from_url("sqlite::memory:").unwrap(), Flavor::SQLite);
+        assert_eq!(Flavor::from_url("jdbc:sqlserver://host;databaseName=app").unwrap(), Flavor::SQLServer);
+        assert!(Flavor::from_url("localhost:5432").is_err());
+    }
+
+    #[test]
+    fn test_with_flavor() {
+        let outer = default_flavor();
+
+        with_flavor(Flavor::SQLite, || {
+            assert_eq!(default_flavor(), Flavor::SQLite);
+            assert_eq!(Flavor::default(), Flavor::SQLite);
+
+            with_flavor(Flavor::PostgreSQL, || {
+                assert_eq!(default_flavor(), Flavor::PostgreSQL);
+            });
+
+            assert_eq!(default_flavor(), Flavor::SQLite);
+        });
+
+        assert_eq!(default_flavor(), outer);
     }
 
     #[test]
@@ -221,10 +584,210 @@ mod tests {
         assert_eq!(flavor.quote("table_name"), "\"table_name\"");
     }
 
+    #[test]
+    fn test_interpolate() {
+        let flavor = Flavor::MySQL;
+        let result = flavor.interpolate("SELECT * FROM t WHERE id = ? AND name = ?", &[1.to_sql_value(), "it's".to_sql_value()]);
+        assert_eq!(result.unwrap(), "SELECT * FROM t WHERE id = 1 AND name = 'it''s'");
+
+        let flavor = Flavor::PostgreSQL;
+        let result = flavor.interpolate("SELECT * FROM t WHERE a = $2 AND b = $1", &[1.to_sql_value(), true.to_sql_value()]);
+        assert_eq!(result.unwrap(), "SELECT * FROM t WHERE a = TRUE AND b = 1");
+
+        let result = flavor.interpolate("SELECT $1::text, '$2', $q$ $2 $q$ -- $2", &["a".to_sql_value()]);
+        assert_eq!(result.unwrap(), "SELECT 'a'::text, '$2', $q$ $2 $q$ -- $2");
+
+        let flavor = Flavor::Oracle;
+        let result = flavor.interpolate("SELECT x::int FROM t WHERE a = :1 /* :2 */", &[1.to_sql_value()]);
+        assert_eq!(result.unwrap(), "SELECT x::int FROM t WHERE a = 1 /* :2 */");
+    }
+
+    #[test]
+    fn test_debug_sql() {
+        let flavor = Flavor::MySQL;
+        let sql = debug_sql(&flavor, "SELECT * FROM t WHERE id = ? AND tag = ?", &[1.to_sql_value(), vec![1u8].to_sql_value()]);
+        assert_eq!(sql, "SELECT * FROM t WHERE id = ? /* ?=1 */ AND tag = ? /* ?=<unsupported> */");
+
+        // NaN 和无穷大不能内联为字面量
+        let result = flavor.interpolate("SELECT ?, ?", &[1.5.to_sql_value(), f64::NAN.to_sql_value()]);
+        assert!(matches!(result, Err(Error::UnsupportedArgs { index: 1, kind: "Double" })));
+
+        let sql = debug_sql(&Flavor::PostgreSQL, "SELECT $1", &[f32::INFINITY.to_sql_value()]);
+        assert_eq!(sql, "SELECT $1 /* ?=<unsupported> */");
+
+        // 注释中的参数值不能结束或嵌套注释
+        let args = ["x*/ OR 1 = 1 /*".to_sql_value(), vec![1u8].to_sql_value()];
+        let sql = debug_sql(&Flavor::PostgreSQL, "SELECT $1, $2", &args);
+        assert_eq!(sql, "SELECT $1 /* ?='x* / OR 1 = 1 / *' */, $2 /* ?=<unsupported> */");
+    }
+
+    #[test]
+    fn test_new_flavors() {
+        assert_eq!(Flavor::DuckDB.interpolate("SELECT $1", &[1.to_sql_value()]).unwrap(), "SELECT 1");
+        assert_eq!(Flavor::BigQuery.placeholder(2), "@p2");
+        assert_eq!(
+            Flavor::BigQuery.interpolate("SELECT * FROM `p.d.t` WHERE a = @p1 AND b = \"@p2\"", &["x".to_sql_value()]).unwrap(),
+            "SELECT * FROM `p.d.t` WHERE a = 'x' AND b = \"@p2\""
+        );
+        assert_eq!(Flavor::Snowflake.quote_if_needed("Users"), "\"Users\"");
+        assert_eq!(Flavor::Doris.quote("t"), "`t`");
+        assert!(Flavor::MariaDB.supports(Feature::Returning));
+        assert!(!Flavor::MariaDB.supports(Feature::UpdateReturning));
+        assert!(Flavor::PostgreSQL.supports(Feature::UpdateReturning));
+        assert_eq!(Flavor::Doris.key_model(), KeyModel::UniqueKey);
+        assert_eq!(Flavor::StarRocks.key_model(), KeyModel::PrimaryKey);
+        assert!(!Flavor::MySQL.supports(Feature::Returning));
+        assert_eq!(Flavor::DuckDB.upsert_syntax(), UpsertSyntax::InsertOrReplace);
+        assert_eq!(Flavor::Snowflake.upsert_syntax(), UpsertSyntax::Merge);
+    }
+
+    #[test]
+    fn test_custom_dialect() {
+        struct DuckLike;
+
+        impl Dialect for DuckLike {
+            fn name(&self) -> &str {
+                "DuckLike"
+            }
+
+            fn placeholder_style(&self) -> PlaceholderStyle {
+                PlaceholderStyle::Numbered("$")
+            }
+
+            fn quote(&self, name: &str) -> String {
+                format!("\"{}\"", name)
+            }
+        }
+
+        static DUCK_LIKE: DuckLike = DuckLike;
+
+        let flavor = Flavor::custom(&DUCK_LIKE);
+        assert_eq!(flavor.to_string(), "DuckLike");
+        assert_eq!(flavor.quote("t"), "\"t\"");
+        assert_eq!(flavor.interpolate("SELECT $1", &["x".to_sql_value()]).unwrap(), "SELECT 'x'");
+        assert!(!flavor.supports(Feature::Returning));
+    }
+
+    #[test]
+    fn test_quote_if_needed() {
+        let flavor = Flavor::MySQL;
+        assert_eq!(flavor.quote_if_needed("user_id"), "user_id");
+        assert_eq!(flavor.quote_if_needed("order"), "`order`");
+        assert_eq!(flavor.quote_if_needed("first name"), "`first name`");
+        assert_eq!(flavor.quote_if_needed("app.user"), "app.`user`");
+
+        let flavor = Flavor::PostgreSQL;
+        assert_eq!(flavor.quote_if_needed("users"), "users");
+        assert_eq!(flavor.quote_if_needed("user"), "\"user\"");
+        assert_eq!(flavor.quote_if_needed("UserName"), "\"UserName\"");
+
+        let flavor = Flavor::Oracle;
+        assert_eq!(flavor.quote_if_needed("USERS"), "USERS");
+        assert_eq!(flavor.quote_if_needed("UserName"), "\"UserName\"");
+
+        assert!(Flavor::MySQL.check_identifier("app.user").is_ok());
+        assert!(Flavor::MySQL.check_identifier("we`ird").is_err());
+        assert!(matches!(
+            Flavor::PostgreSQL.check_identifier("app..user"),
+            Err(Error::InvalidIdentifier { name }) if name == "app..user"
+        ));
+    }
+
+    #[test]
+    fn test_supports() {
+        assert!(Flavor::PostgreSQL.supports(Feature::FullJoin));
+        assert!(!Flavor::MySQL.supports(Feature::FullJoin));
+        assert!(!Flavor::SQLServer.supports(Feature::IfNotExists));
+
+        let mut ctb = Flavor::SQLServer.new_create_table_builder();
+        ctb.create_temp_table("t").if_not_exists().define(vec!["id".to_string(), "INT".to_string()]);
+        assert_eq!(
+            ctb.string().unwrap(),
+            "IF OBJECT_ID('tempdb..#t', 'U') IS NULL CREATE TABLE #t (id INT)"
+        );
+
+        ctb.set_build_mode(BuildMode::Strict);
+        let err = ctb.build().unwrap_err();
+        assert!(matches!(err, Error::Build { builder: "CreateTableBuilder", .. }));
+        assert!(matches!(err.root(), Error::UnsupportedFeature { feature: Feature::TemporaryTable, .. }));
+        assert_eq!(err.to_string(), "CreateTableBuilder: TEMPORARY TABLE is not supported by SQLServer");
+
+        let mut ctb = Flavor::Oracle.new_create_table_builder();
+        ctb.create_temp_table("t").define(vec!["id".to_string(), "INT".to_string()]);
+        assert_eq!(ctb.string().unwrap(), "CREATE GLOBAL TEMPORARY TABLE t (id INT)");
+
+        ctb.set_build_mode(BuildMode::Strict);
+        let (sql, _) = ctb.build_with_flavor(Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(sql, "CREATE TEMPORARY TABLE t (id INT)");
+    }
+
+    #[test]
+    fn test_insert_builder() {
+        let mut ib = Flavor::PostgreSQL.new_insert_builder();
+        ib.set_table("t");
+        ib.cols(&["id", "name"])
+            .values(vec![1.to_sql_value(), "a".to_sql_value()])
+            .on_conflict_do_nothing()
+            .returning(&["id"]);
+        ib.set_build_mode(BuildMode::Strict);
+        assert_eq!(
+            ib.build().unwrap().0,
+            "INSERT INTO t (id, name) VALUES ($1, $2) ON CONFLICT DO NOTHING RETURNING id"
+        );
+
+        let err = ib.build_with_flavor(Flavor::MySQL, Vec::new()).unwrap_err();
+        assert!(matches!(err.root(), Error::UnsupportedFeature { feature: Feature::OnConflict, .. }));
+
+        let err = ib.build_with_flavor(Flavor::SQLServer, Vec::new()).unwrap_err();
+        assert!(matches!(err.root(), Error::UnsupportedFeature { feature: Feature::OnConflict, .. }));
+
+        ib.set_build_mode(BuildMode::Lenient);
+        let (sql, args) = ib.build_with_flavor(Flavor::MySQL, Vec::new()).unwrap();
+        assert_eq!(sql, "INSERT IGNORE INTO t (id, name) VALUES (?, ?)");
+        assert_eq!(args.len(), 2);
+
+        let (sql, _) = ib.build_with_flavor(Flavor::MariaDB, Vec::new()).unwrap();
+        assert_eq!(sql, "INSERT IGNORE INTO t (id, name) VALUES (?, ?) RETURNING id");
+
+        let (sql, _) = ib.build_with_flavor(Flavor::Oracle, Vec::new()).unwrap();
+        assert_eq!(sql, "INSERT INTO t (id, name) VALUES (:1, :2)");
+
+        let mut ib = InsertBuilder::new();
+        Flavor::Oracle.prepare_insert_ignore("t$1", &mut ib);
+        ib.cols(&["id"]).values(vec![1.to_sql_value()]);
+        ib.set_build_mode(BuildMode::Strict);
+        let err = ib.build_with_flavor(Flavor::Oracle, Vec::new()).unwrap_err();
+        assert!(matches!(err.root(), Error::UnsupportedFeature { feature: Feature::OnConflict, .. }));
+
+        ib.set_build_mode(BuildMode::Lenient);
+        let (sql, _) = ib.build_with_flavor(Flavor::Oracle, Vec::new()).unwrap();
+        assert_eq!(sql, "INSERT INTO t$1 (id) VALUES (:1)");
+
+        let (sql, _) = ib.build_with_flavor(Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(sql, "INSERT INTO t$1 (id) VALUES ($1) ON CONFLICT DO NOTHING");
+
+        let mut ib = Flavor::SQLServer.new_insert_builder();
+        ib.set_table("t");
+        ib.cols(&["name"]).values(vec!["a".to_sql_value()]).returning(&["id"]);
+        assert_eq!(ib.build().unwrap().0, "INSERT INTO t (name) OUTPUT INSERTED.id VALUES (@p1)");
+
+        ib.set_build_mode(BuildMode::Strict);
+        let err = ib.build().unwrap_err();
+        assert!(matches!(err.root(), Error::UnsupportedFeature { feature: Feature::Returning, .. }));
+        assert_eq!(err.to_string(), "InsertBuilder: RETURNING is not supported by SQLServer");
+    }
+
     #[test]
     fn test_interpolate_error_handling() {
         let flavor = Flavor::MySQL;
         let result = flavor.interpolate("SELECT * FROM table WHERE id = ?", &[]);
-        assert_eq!(result, Err(InterpolateError::MissingArgs));
+        assert!(matches!(result, Err(Error::MissingArgs { expected: 1, got: 0, at: 31 })));
+
+        let result = Flavor::PostgreSQL.interpolate("SELECT '$1', $1, $3", &[1.to_sql_value(), 2.to_sql_value()]);
+        assert!(matches!(result, Err(Error::MissingArgs { expected: 3, got: 2, at: 17 })));
+
+        let result = flavor.interpolate("SELECT ?, ?", &[1.to_sql_value(), vec![1u8].to_sql_value()]);
+        assert!(matches!(result, Err(Error::UnsupportedArgs { index: 1, kind: "Bytes" })));
+        assert_eq!(result.unwrap_err().to_string(), "unsupported arg #1 of type Bytes");
     }
 }
@@ -0,0 +1,395 @@
+use crate::flavor::Flavor;
+use crate::lexer::{Lexer, TokenKind};
+
+// 缩进宽度
+const INDENT: usize = 2;
+
+// 会另起一行的子句关键字，较长的短语需要排在前面
+const CLAUSES: &[&[&str]] = &[
+    &["ON", "DUPLICATE", "KEY", "UPDATE"],
+    &["ON", "CONFLICT"],
+    &["CREATE", "GLOBAL", "TEMPORARY", "TABLE"],
+    &["CREATE", "TEMPORARY", "TABLE"],
+    &["CREATE", "TABLE"],
+    &["INSERT", "IGNORE", "INTO"],
+    &["INSERT", "OR", "IGNORE", "INTO"],
+    &["INSERT", "OR", "REPLACE", "INTO"],
+    &["INSERT", "INTO"],
+    &["REPLACE", "INTO"],
+    &["DELETE", "FROM"],
+    &["WITH", "RECURSIVE"],
+    &["WITH"],
+    &["SELECT"],
+    &["FROM"],
+    &["LEFT", "OUTER", "JOIN"],
+    &["RIGHT", "OUTER", "JOIN"],
+    &["FULL", "OUTER", "JOIN"],
+    &["LEFT", "JOIN"],
+    &["RIGHT", "JOIN"],
+    &["FULL", "JOIN"],
+    &["INNER", "JOIN"],
+    &["CROSS", "JOIN"],
+    &["JOIN"],
+    &["WHERE"],
+    &["GROUP", "BY"],
+    &["HAVING"],
+    &["WINDOW"],
+    &["UNION", "ALL"],
+    &["UNION"],
+    &["INTERSECT"],
+    &["EXCEPT"],
+    &["ORDER", "BY"],
+    &["LIMIT"],
+    &["OFFSET"],
+    &["FOR", "NO", "KEY", "UPDATE"],
+    &["FOR", "KEY", "SHARE"],
+    &["FOR", "UPDATE"],
+    &["FOR", "SHARE"],
+    &["UPDATE"],
+    &["SET"],
+    &["VALUES"],
+    &["RETURNING"],
+];
+
+// 跟在特定单词后面时不是子句的关键字
+// CHARACTER SET 是表选项，不是 UPDATE 的 SET 子句；IS DISTINCT FROM 是比较运算符
+const NOT_CLAUSES: &[(&str, &str)] = &[("CHARACTER", "SET"), ("DISTINCT", "FROM")];
+
+// 列表型子句，其中顶层的逗号会换行并与第一项对齐
+const LIST_CLAUSES: &[&str] = &[
+    "ON DUPLICATE KEY UPDATE",
+    "WITH",
+    "WITH RECURSIVE",
+    "SELECT",
+    "FROM",
+    "GROUP BY",
+    "ORDER BY",
+    "WINDOW",
+    "SET",
+    "VALUES",
+    "RETURNING",
+];
+
+// 分词结果
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    LineComment(String),
+    BlockComment(String),
+    Punct(char),
+}
+
+// 带有前导空白信息的分词
+struct Spanned {
+    token: Token,
+    spaced: bool,
+}
+
+// 括号内部的排版方式
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FrameKind {
+    // 语句顶层或子查询，子句关键字会换行
+    Block,
+    // CREATE TABLE 的列定义，每个定义独占一行
+    Definitions,
+    // 函数调用、IN 列表等，保持在同一行
+    Inline,
+}
+
+struct Frame {
+    kind: FrameKind,
+    indent: usize,
+    close_indent: usize,
+    list_indent: Option<usize>,
+    create_table: bool,
+}
+
+// 将单行 SQL 格式化为便于阅读的多行形式
+// 子句换行，子查询和 CTE 缩进，SELECT 列表等按第一项对齐
+pub fn format_sql(sql: &str, flavor: &Flavor) -> String {
+    let tokens = tokenize(sql, flavor);
+    let mut out = String::with_capacity(sql.len() * 2);
+    let mut frames = vec![Frame {
+        kind: FrameKind::Block,
+        indent: 0,
+        close_indent: 0,
+        list_indent: None,
+        create_table: false,
+    }];
+    let mut line_start = true;
+    let mut line_indent = 0;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let Spanned { token, spaced } = &tokens[i];
+        let frame = frames.last_mut().unwrap();
+
+        match token {
+            Token::Word(_) if frame.kind == FrameKind::Block => {
+                if let Some(n) = match_clause(&tokens, i) {
+                    let phrase = tokens[i..i + n]
+                        .iter()
+                        .map(|t| match &t.token {
+                            Token::Word(w) => w.as_str(),
+                            _ => "",
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    let upper = phrase.to_ascii_uppercase();
+
+                    if !line_start {
+                        line_indent = newline(&mut out, frame.indent);
+                    }
+
+                    out.push_str(&phrase);
+                    frame.list_indent = if LIST_CLAUSES.contains(&upper.as_str()) {
+                        Some(line_indent + phrase.len() + 1)
+                    } else {
+                        None
+                    };
+                    frame.create_table = upper.starts_with("CREATE");
+                    line_start = false;
+                    i += n;
+                    continue;
+                }
+
+                write_token(&mut out, token, *spaced, line_start);
+                line_start = false;
+            }
+            Token::Punct('(') => {
+                let kind = if frame.kind == FrameKind::Block && starts_query(&tokens, i + 1) {
+                    FrameKind::Block
+                } else if frame.kind == FrameKind::Block && frame.create_table {
+                    frame.create_table = false;
+                    FrameKind::Definitions
+                } else {
+                    FrameKind::Inline
+                };
+
+                write_token(&mut out, token, *spaced, line_start);
+                frames.push(Frame {
+                    kind,
+                    indent: line_indent + INDENT,
+                    close_indent: line_indent,
+                    list_indent: None,
+                    create_table: false,
+                });
+
+                if kind == FrameKind::Definitions {
+                    line_indent = newline(&mut out, line_indent + INDENT);
+                    line_start = true;
+                } else {
+                    line_start = false;
+                }
+            }
+            Token::Punct(')') => {
+                let closed = if frames.len() > 1 { frames.pop() } else { None };
+
+                match closed {
+                    Some(f) if f.kind != FrameKind::Inline => {
+                        line_indent = newline(&mut out, f.close_indent);
+                        out.push(')');
+                    }
+                    _ => write_token(&mut out, token, *spaced, line_start),
+                }
+
+                line_start = false;
+            }
+            Token::Punct(',') => {
+                out.push(',');
+
+                match (frame.kind, frame.list_indent) {
+                    (FrameKind::Definitions, _) => {
+                        line_indent = newline(&mut out, frame.indent);
+                        line_start = true;
+                    }
+                    (FrameKind::Block, Some(indent)) => {
+                        line_indent = newline(&mut out, indent);
+                        line_start = true;
+                    }
+                    _ => line_start = false,
+                }
+            }
+            Token::Punct(';') => {
+                out.push(';');
+                out.push('\n');
+                frames.truncate(1);
+                frames[0].list_indent = None;
+                line_start = true;
+                line_indent = 0;
+            }
+            Token::LineComment(_) => {
+                write_token(&mut out, token, *spaced, line_start);
+                line_indent = newline(&mut out, frame.indent);
+                line_start = true;
+            }
+            _ => {
+                write_token(&mut out, token, *spaced, line_start);
+                line_start = false;
+            }
+        }
+
+        i += 1;
+    }
+
+    while out.ends_with(|c: char| c.is_whitespace()) {
+        out.pop();
+    }
+
+    out
+}
+
+// 换行并写入缩进，返回新行的缩进宽度
+fn newline(out: &mut String, indent: usize) -> usize {
+    while out.ends_with(' ') {
+        out.pop();
+    }
+
+    out.push('\n');
+    out.push_str(&" ".repeat(indent));
+    indent
+}
+
+// 按原始空白写入一个分词
+fn write_token(out: &mut String, token: &Token, spaced: bool, line_start: bool) {
+    if spaced && !line_start {
+        out.push(' ');
+    }
+
+    match token {
+        Token::Word(s) | Token::Quoted(s) | Token::LineComment(s) | Token::BlockComment(s) => {
+            out.push_str(s)
+        }
+        Token::Punct(c) => out.push(*c),
+    }
+}
+
+// 判断从 i 开始的分词是否为子句关键字，返回其包含的单词数
+fn match_clause(tokens: &[Spanned], i: usize) -> Option<usize> {
+    if let (Some(Token::Word(prev)), Token::Word(word)) = (i.checked_sub(1).map(|j| &tokens[j].token), &tokens[i].token) {
+        let follows = NOT_CLAUSES
+            .iter()
+            .any(|(before, keyword)| prev.eq_ignore_ascii_case(before) && word.eq_ignore_ascii_case(keyword));
+
+        if follows {
+            return None;
+        }
+    }
+
+    CLAUSES
+        .iter()
+        .find(|phrase| {
+            phrase.iter().enumerate().all(|(j, kw)| match tokens.get(i + j) {
+                Some(Spanned {
+                    token: Token::Word(w),
+                    ..
+                }) => w.eq_ignore_ascii_case(kw),
+                _ => false,
+            })
+        })
+        .map(|phrase| phrase.len())
+}
+
+// 判断括号内是否为子查询
+fn starts_query(tokens: &[Spanned], i: usize) -> bool {
+    match tokens.get(i) {
+        Some(Spanned {
+            token: Token::Word(w),
+            ..
+        }) => w.eq_ignore_ascii_case("SELECT") || w.eq_ignore_ascii_case("WITH"),
+        _ => false,
+    }
+}
+
+// 将 SQL 切分为单词、引号内容、注释和标点
+fn tokenize(sql: &str, flavor: &Flavor) -> Vec<Spanned> {
+    let mut tokens = Vec::new();
+    let mut spaced = false;
+
+    for lexeme in Lexer::new(sql, flavor.capabilities()) {
+        let text = lexeme.text.to_string();
+        let token = match lexeme.kind {
+            TokenKind::Code => {
+                split_code(lexeme.text, &mut tokens, &mut spaced);
+                continue;
+            }
+            TokenKind::String | TokenKind::Identifier => Token::Quoted(text),
+            TokenKind::Comment if text.starts_with("/*") => Token::BlockComment(text),
+            TokenKind::Comment => Token::LineComment(text),
+        };
+
+        tokens.push(Spanned { token, spaced });
+        spaced = false;
+    }
+
+    tokens
+}
+
+// 将一段代码切分为单词和标点
+fn split_code(code: &str, tokens: &mut Vec<Spanned>, spaced: &mut bool) {
+    let mut word_start = None;
+
+    for (i, c) in code.char_indices() {
+        let is_punct = matches!(c, '(' | ')' | ',' | ';');
+
+        if c.is_whitespace() || is_punct {
+            if let Some(start) = word_start.take() {
+                tokens.push(Spanned {
+                    token: Token::Word(code[start..i].to_string()),
+                    spaced: *spaced,
+                });
+                *spaced = false;
+            }
+
+            if is_punct {
+                tokens.push(Spanned {
+                    token: Token::Punct(c),
+                    spaced: *spaced,
+                });
+                *spaced = false;
+            } else {
+                *spaced = true;
+            }
+        } else if word_start.is_none() {
+            word_start = Some(i);
+        }
+    }
+
+    if let Some(start) = word_start {
+        tokens.push(Spanned {
+            token: Token::Word(code[start..].to_string()),
+            spaced: *spaced,
+        });
+        *spaced = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_select() {
+        let sql = "SELECT id, name FROM users WHERE id IN (SELECT user_id FROM orders WHERE total > ?) ORDER BY id DESC, name LIMIT 10";
+        let expected = "SELECT id,\n       name\nFROM users\nWHERE id IN (\n  SELECT user_id\n  FROM orders\n  WHERE total > ?\n)\nORDER BY id DESC,\n         name\nLIMIT 10";
+        assert_eq!(format_sql(sql, &Flavor::MySQL), expected);
+    }
+
+    #[test]
+    fn test_format_create_table() {
+        let sql = "CREATE TABLE IF NOT EXISTS users (id BIGINT(20) NOT NULL, name VARCHAR(255) COMMENT 'a, b') DEFAULT CHARACTER SET utf8mb4";
+        let expected = "CREATE TABLE IF NOT EXISTS users (\n  id BIGINT(20) NOT NULL,\n  name VARCHAR(255) COMMENT 'a, b'\n) DEFAULT CHARACTER SET utf8mb4";
+        assert_eq!(format_sql(sql, &Flavor::MySQL), expected);
+
+        let sql = "CREATE GLOBAL TEMPORARY TABLE t (id INT)";
+        assert_eq!(format_sql(sql, &Flavor::Oracle), "CREATE GLOBAL TEMPORARY TABLE t (\n  id INT\n)");
+    }
+
+    #[test]
+    fn test_format_operators() {
+        let sql = "SELECT id FROM users WHERE name IS DISTINCT FROM ? FOR UPDATE";
+        let expected = "SELECT id\nFROM users\nWHERE name IS DISTINCT FROM ?\nFOR UPDATE";
+        assert_eq!(format_sql(sql, &Flavor::PostgreSQL), expected);
+    }
+}
@@ -0,0 +1,443 @@
+use crate::array;
+use crate::builder::BuildMode;
+use crate::dialect::Feature;
+use crate::error::Error;
+use crate::expr::{BinaryOp, Expr};
+use crate::flavor::Flavor;
+use crate::json;
+use crate::search::{self, SearchMode};
+use crate::value::Value;
+
+// 日期时间的单位
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateUnit {
+    Year,
+    Month,
+    Day,
+    Hour,
+    Minute,
+    Second,
+}
+
+impl DateUnit {
+    // 返回小写的单位名称，例如 day
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DateUnit::Year => "year",
+            DateUnit::Month => "month",
+            DateUnit::Day => "day",
+            DateUnit::Hour => "hour",
+            DateUnit::Minute => "minute",
+            DateUnit::Second => "second",
+        }
+    }
+
+    // 返回大写的单位关键字，例如 DAY
+    fn keyword(&self) -> String {
+        self.as_str().to_ascii_uppercase()
+    }
+}
+
+// 各方言写法不同的函数
+#[derive(Debug, Clone, PartialEq)]
+pub enum Function {
+    // 当前时间
+    Now,
+    // 连接多个字符串
+    Concat,
+    // 第一个参数为 NULL 时返回第二个参数
+    IfNull,
+    // 截取字符串，参数为字符串、从 1 开始的起始位置和长度
+    Substring,
+    // 截断到指定的单位
+    DateTrunc(DateUnit),
+    // 加上若干个单位的时间，数量为负数时表示减去
+    DateAdd(i64, DateUnit),
+    // 用分隔符连接分组中的字符串
+    StringAgg(String),
+    // 按路径提取 JSON 中的值，见 json::json_extract
+    JsonExtract(String),
+    // 判断 JSON 文档是否包含另一个文档
+    JsonContains,
+    // 判断 JSON 对象是否包含顶层的键
+    JsonHasKey(String),
+    // JSON 数组的长度
+    JsonArrayLength,
+    // 全文检索的匹配条件，最后一个参数是查询字符串，见 search::match_against
+    Match(SearchMode),
+    // 全文检索的相关度，参数与 Match 相同
+    MatchRank(SearchMode),
+    // 数组包含元素，参数为数组和元素，见 array::array_contains
+    ArrayContains,
+    // 两个数组有相同的元素
+    ArrayOverlap,
+    // 等于数组中的任意一个元素，参数为表达式和数组
+    EqualAny,
+}
+
+impl Function {
+    // 按 flavor 改写为具体的表达式
+    // 参数个数不符合函数的要求时，改写为标准 SQL 的函数调用
+    // mode 决定 Flavor 不支持函数需要的特性时是返回错误还是原样生成
+    pub(crate) fn lower(&self, flavor: &Flavor, mode: BuildMode, args: &[Expr]) -> Result<Expr, Error> {
+        let expr = match (self, args) {
+            (Function::Now, []) => now_for(flavor),
+            (Function::Concat, _) => concat_for(flavor, args),
+            (Function::IfNull, [_, _]) => {
+                let name = match flavor {
+                    Flavor::MySQL
+                    | Flavor::MySQL57
+                    | Flavor::MariaDB
+                    | Flavor::Doris
+                    | Flavor::StarRocks
+                    | Flavor::SQLite
+                    | Flavor::ClickHouse
+                    | Flavor::BigQuery
+                    | Flavor::Snowflake => "IFNULL",
+                    Flavor::Oracle | Flavor::Informix => "NVL",
+                    Flavor::SQLServer => "ISNULL",
+                    _ => "COALESCE",
+                };
+                Expr::func(name, args.to_vec())
+            }
+            (Function::Substring, [_, _, _]) => {
+                let name = match flavor {
+                    Flavor::Oracle
+                    | Flavor::SQLite
+                    | Flavor::Informix
+                    | Flavor::BigQuery
+                    | Flavor::Presto
+                    | Flavor::Snowflake => "SUBSTR",
+                    _ => "SUBSTRING",
+                };
+                Expr::func(name, args.to_vec())
+            }
+            // 不支持时宽松模式生成标准 SQL 的 DATE_TRUNC 和 + INTERVAL
+            (Function::DateTrunc(unit), [expr]) => match flavor.check_feature(Feature::DateTrunc, mode)? {
+                true => date_trunc_for(flavor, *unit, expr.clone()),
+                false => Expr::func("DATE_TRUNC", [Expr::Raw(format!("'{}'", unit.as_str())), expr.clone()]),
+            },
+            (Function::DateAdd(amount, unit), [expr]) => match flavor.check_feature(Feature::DateAdd, mode)? {
+                true => date_add_for(flavor, expr.clone(), *amount, *unit),
+                false => standard_date_add(expr.clone(), *amount, *unit),
+            },
+            (Function::StringAgg(separator), [expr]) => {
+                string_agg_for(flavor, expr.clone(), &literal(flavor, separator)?)
+            }
+            (Function::JsonExtract(path), [expr]) => json::lower_extract(flavor, expr.clone(), path)?,
+            (Function::JsonContains, [expr, candidate]) => {
+                json::lower_contains(flavor, mode, expr.clone(), candidate.clone())?
+            }
+            (Function::JsonHasKey(key), [expr]) => json::lower_has_key(flavor, expr.clone(), key)?,
+            (Function::JsonArrayLength, [expr]) => json::lower_array_length(flavor, mode, expr.clone())?,
+            (Function::Match(search), [columns @ .., query]) if !columns.is_empty() => {
+                search::lower_match(flavor, mode, *search, columns, query)?
+            }
+            (Function::MatchRank(search), [columns @ .., query]) if !columns.is_empty() => {
+                search::lower_rank(flavor, mode, *search, columns, query)?
+            }
+            (Function::ArrayContains, [array, element]) => array::lower_contains(flavor, array, element)?,
+            (Function::ArrayOverlap, [array, other]) => array::lower_overlap(flavor, array, other)?,
+            (Function::EqualAny, [expr, values]) => array::lower_equal_any(flavor, expr, values)?,
+            _ => Expr::func(self.standard_name(), args.to_vec()),
+        };
+
+        Ok(expr)
+    }
+
+    // 标准 SQL 中的函数名
+    fn standard_name(&self) -> &'static str {
+        match self {
+            Function::Now => "CURRENT_TIMESTAMP",
+            Function::Concat => "CONCAT",
+            Function::IfNull => "COALESCE",
+            Function::Substring => "SUBSTRING",
+            Function::DateTrunc(_) => "DATE_TRUNC",
+            Function::DateAdd(..) => "DATE_ADD",
+            Function::StringAgg(_) => "STRING_AGG",
+            Function::JsonExtract(_) => "JSON_VALUE",
+            Function::JsonContains => "JSON_CONTAINS",
+            Function::JsonHasKey(_) => "JSON_EXISTS",
+            Function::JsonArrayLength => "JSON_ARRAY_LENGTH",
+            Function::Match(_) | Function::MatchRank(_) => "MATCH",
+            Function::ArrayContains => "ARRAY_CONTAINS",
+            Function::ArrayOverlap => "ARRAYS_OVERLAP",
+            Function::EqualAny => "ANY",
+        }
+    }
+}
+
+// 返回当前时间：NOW()、GETDATE()、SYSDATE、now64() 等
+pub fn now() -> Expr {
+    call(Function::Now, Vec::new())
+}
+
+// 连接多个字符串：CONCAT(a, b)、a || b 或 a + b
+pub fn concat<E: Into<Expr>>(exprs: impl IntoIterator<Item = E>) -> Expr {
+    call(Function::Concat, exprs.into_iter().map(Into::into).collect())
+}
+
+// expr 为 NULL 时返回 fallback：IFNULL、COALESCE、NVL 或 ISNULL
+pub fn if_null(expr: impl Into<Expr>, fallback: impl Into<Expr>) -> Expr {
+    call(Function::IfNull, vec![expr.into(), fallback.into()])
+}
+
+// 从第 start 个字符（从 1 开始）开始截取 len 个字符：SUBSTRING 或 SUBSTR
+pub fn substring(expr: impl Into<Expr>, start: impl Into<Expr>, len: impl Into<Expr>) -> Expr {
+    call(Function::Substring, vec![expr.into(), start.into(), len.into()])
+}
+
+// 将日期时间截断到 unit，例如按月截断为当月第一天的零点
+pub fn date_trunc(unit: DateUnit, expr: impl Into<Expr>) -> Expr {
+    call(Function::DateTrunc(unit), vec![expr.into()])
+}
+
+// 日期时间加上 amount 个 unit，amount 为负数时表示减去
+pub fn date_add(expr: impl Into<Expr>, amount: i64, unit: DateUnit) -> Expr {
+    call(Function::DateAdd(amount, unit), vec![expr.into()])
+}
+
+// 用 separator 连接分组中的字符串：GROUP_CONCAT、STRING_AGG、LISTAGG、groupArray 等
+// separator 在大多数方言中必须是常量，因此内联为字面量而不是绑定为参数
+pub fn string_agg(expr: impl Into<Expr>, separator: &str) -> Expr {
+    call(Function::StringAgg(separator.to_string()), vec![expr.into()])
+}
+
+pub(crate) fn call(func: Function, args: Vec<Expr>) -> Expr {
+    Expr::Call { func, args }
+}
+
+// 使用方言的规则将字符串编码为字面量
+pub(crate) fn literal(flavor: &Flavor, s: &str) -> Result<Expr, Error> {
+    let mut buf = String::new();
+    flavor.dialect().encode_literal(&mut buf, &Value::String(s.to_string()))?;
+    Ok(Expr::Raw(buf))
+}
+
+fn now_for(flavor: &Flavor) -> Expr {
+    match flavor {
+        Flavor::MySQL
+        | Flavor::MySQL57
+        | Flavor::MariaDB
+        | Flavor::Doris
+        | Flavor::StarRocks
+        | Flavor::PostgreSQL
+        | Flavor::DuckDB
+        | Flavor::Presto => Expr::func("NOW", Vec::<Expr>::new()),
+        Flavor::SQLServer => Expr::func("GETDATE", Vec::<Expr>::new()),
+        Flavor::Oracle => Expr::raw("SYSDATE"),
+        Flavor::ClickHouse => Expr::func("now64", Vec::<Expr>::new()),
+        Flavor::CQL => Expr::func("toTimestamp", [Expr::func("now", Vec::<Expr>::new())]),
+        Flavor::Informix => Expr::raw("CURRENT"),
+        _ => Expr::raw("CURRENT_TIMESTAMP"),
+    }
+}
+
+fn concat_for(flavor: &Flavor, args: &[Expr]) -> Expr {
+    let op = match flavor {
+        Flavor::SQLServer => BinaryOp::Add,
+        Flavor::PostgreSQL
+        | Flavor::SQLite
+        | Flavor::Oracle
+        | Flavor::DuckDB
+        | Flavor::Informix
+        | Flavor::CQL
+        | Flavor::Custom(_) => BinaryOp::Concat,
+        _ => return Expr::func("CONCAT", args.to_vec()),
+    };
+
+    args.iter()
+        .cloned()
+        .reduce(|left, right| left.binary(op, right))
+        .unwrap_or_else(|| Expr::raw("''"))
+}
+
+fn date_trunc_for(flavor: &Flavor, unit: DateUnit, expr: Expr) -> Expr {
+    let quoted = Expr::Raw(format!("'{}'", unit.as_str()));
+
+    match flavor {
+        Flavor::MySQL | Flavor::MySQL57 | Flavor::MariaDB => {
+            let format = match unit {
+                DateUnit::Year => "%Y-01-01 00:00:00",
+                DateUnit::Month => "%Y-%m-01 00:00:00",
+                DateUnit::Day => "%Y-%m-%d 00:00:00",
+                DateUnit::Hour => "%Y-%m-%d %H:00:00",
+                DateUnit::Minute => "%Y-%m-%d %H:%i:00",
+                DateUnit::Second => "%Y-%m-%d %H:%i:%s",
+            };
+            Expr::func("DATE_FORMAT", [expr, Expr::Raw(format!("'{}'", format))]).cast("DATETIME")
+        }
+        Flavor::SQLite => {
+            let format = match unit {
+                DateUnit::Year => "%Y-01-01 00:00:00",
+                DateUnit::Month => "%Y-%m-01 00:00:00",
+                DateUnit::Day => "%Y-%m-%d 00:00:00",
+                DateUnit::Hour => "%Y-%m-%d %H:00:00",
+                DateUnit::Minute => "%Y-%m-%d %H:%M:00",
+                DateUnit::Second => "%Y-%m-%d %H:%M:%S",
+            };
+            Expr::func("strftime", [Expr::Raw(format!("'{}'", format)), expr])
+        }
+        Flavor::SQLServer => Expr::func("DATETRUNC", [Expr::raw(unit.as_str()), expr]),
+        Flavor::BigQuery => Expr::func("TIMESTAMP_TRUNC", [expr, Expr::Raw(unit.keyword())]),
+        Flavor::Doris => Expr::func("DATE_TRUNC", [expr, quoted]),
+        // TRUNC 不支持按秒截断，Oracle 转换为精确到秒的 DATE，Informix 使用 EXTEND
+        Flavor::Oracle if unit == DateUnit::Second => expr.cast("DATE"),
+        Flavor::Informix if unit == DateUnit::Second => Expr::func("EXTEND", [expr, Expr::raw("YEAR TO SECOND")]),
+        Flavor::Oracle | Flavor::Informix => {
+            let format = match unit {
+                DateUnit::Year => "YYYY",
+                DateUnit::Month => "MM",
+                DateUnit::Day => "DD",
+                DateUnit::Hour => "HH",
+                _ => "MI",
+            };
+            Expr::func("TRUNC", [expr, Expr::Raw(format!("'{}'", format))])
+        }
+        _ => Expr::func("DATE_TRUNC", [quoted, expr]),
+    }
+}
+
+fn date_add_for(flavor: &Flavor, expr: Expr, amount: i64, unit: DateUnit) -> Expr {
+    let interval = Expr::Raw(format!("INTERVAL {} {}", amount, unit.keyword()));
+
+    match flavor {
+        Flavor::MySQL | Flavor::MySQL57 | Flavor::MariaDB | Flavor::Doris | Flavor::StarRocks => {
+            Expr::func("DATE_ADD", [expr, interval])
+        }
+        Flavor::BigQuery => Expr::func("TIMESTAMP_ADD", [expr, interval]),
+        Flavor::SQLServer | Flavor::Snowflake => {
+            Expr::func("DATEADD", [Expr::raw(unit.as_str()), Expr::Raw(amount.to_string()), expr])
+        }
+        Flavor::Presto => Expr::func(
+            "date_add",
+            [Expr::Raw(format!("'{}'", unit.as_str())), Expr::Raw(amount.to_string()), expr],
+        ),
+        Flavor::ClickHouse => Expr::func("date_add", [Expr::Raw(unit.keyword()), Expr::Raw(amount.to_string()), expr]),
+        Flavor::SQLite => Expr::func("datetime", [expr, Expr::Raw(format!("'{:+} {}'", amount, unit.as_str()))]),
+        // 按天以下的单位使用 NUMTODSINTERVAL，避免 INTERVAL 字面量的精度限制
+        Flavor::Oracle => match unit {
+            DateUnit::Year => Expr::func("ADD_MONTHS", [expr, Expr::Raw(amount.saturating_mul(12).to_string())]),
+            DateUnit::Month => Expr::func("ADD_MONTHS", [expr, Expr::Raw(amount.to_string())]),
+            _ => expr
+                + Expr::func(
+                    "NUMTODSINTERVAL",
+                    [Expr::Raw(amount.to_string()), Expr::Raw(format!("'{}'", unit.keyword()))],
+                ),
+        },
+        Flavor::Informix => expr + Expr::Raw(format!("INTERVAL({}) {} TO {}", amount, unit.keyword(), unit.keyword())),
+        _ => standard_date_add(expr, amount, unit),
+    }
+}
+
+fn standard_date_add(expr: Expr, amount: i64, unit: DateUnit) -> Expr {
+    expr + Expr::Raw(format!("INTERVAL '{} {}'", amount, unit.as_str()))
+}
+
+pub(crate) fn clause(expr: Expr, keyword: &'static str, arg: Expr) -> Expr {
+    Expr::Clause {
+        expr: Box::new(expr),
+        keyword,
+        arg: Box::new(arg),
+    }
+}
+
+fn string_agg_for(flavor: &Flavor, expr: Expr, separator: &Expr) -> Expr {
+    match flavor {
+        Flavor::MySQL | Flavor::MySQL57 | Flavor::MariaDB => {
+            Expr::func("GROUP_CONCAT", [clause(expr, "SEPARATOR", separator.clone())])
+        }
+        Flavor::Doris | Flavor::StarRocks | Flavor::SQLite => Expr::func("GROUP_CONCAT", [expr, separator.clone()]),
+        Flavor::Oracle => clause(
+            Expr::func("LISTAGG", [expr, separator.clone()]),
+            "WITHIN GROUP",
+            Expr::raw("(ORDER BY NULL)"),
+        ),
+        Flavor::Snowflake => Expr::func("LISTAGG", [expr, separator.clone()]),
+        Flavor::ClickHouse => Expr::func("arrayStringConcat", [Expr::func("groupArray", [expr]), separator.clone()]),
+        Flavor::Presto => Expr::func("array_join", [Expr::func("array_agg", [expr]), separator.clone()]),
+        _ => Expr::func("STRING_AGG", [expr, separator.clone()]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::Args;
+
+    fn render(flavor: Flavor, expr: Expr) -> String {
+        let mut args = Args::new();
+        let format = args.add_expr(expr);
+        args.compile_with_flavor(&format, flavor, Vec::new()).unwrap().0
+    }
+
+    #[test]
+    fn test_func() {
+        let cases = [
+            (Flavor::MySQL, "NOW()"),
+            (Flavor::SQLServer, "GETDATE()"),
+            (Flavor::Oracle, "SYSDATE"),
+            (Flavor::ClickHouse, "now64()"),
+            (Flavor::SQLite, "CURRENT_TIMESTAMP"),
+        ];
+        for (flavor, expected) in cases {
+            assert_eq!(render(flavor, now()), expected);
+        }
+
+        let name = || concat([Expr::col("first"), Expr::val(" "), Expr::col("last")]);
+        assert_eq!(render(Flavor::MySQL, name()), "CONCAT(first, ?, last)");
+        assert_eq!(render(Flavor::PostgreSQL, name().eq("a b")), "first || $1 || last = $2");
+        assert_eq!(render(Flavor::SQLServer, name()), "first + @p1 + last");
+
+        let nick = || if_null(Expr::col("nick"), Expr::col("name"));
+        assert_eq!(render(Flavor::MySQL, nick()), "IFNULL(nick, name)");
+        assert_eq!(render(Flavor::Oracle, nick()), "NVL(nick, name)");
+        assert_eq!(render(Flavor::SQLServer, nick()), "ISNULL(nick, name)");
+        assert_eq!(render(Flavor::PostgreSQL, nick()), "COALESCE(nick, name)");
+
+        assert_eq!(render(Flavor::SQLite, substring(Expr::col("s"), 1, 3)), "SUBSTR(s, ?, ?)");
+        assert_eq!(render(Flavor::MySQL, substring(Expr::col("s"), 1, 3)), "SUBSTRING(s, ?, ?)");
+
+        let month = || date_trunc(DateUnit::Month, Expr::col("created_at"));
+        assert_eq!(render(Flavor::PostgreSQL, month()), "DATE_TRUNC('month', created_at)");
+        assert_eq!(render(Flavor::MySQL, month()), "CAST(DATE_FORMAT(created_at, '%Y-%m-01 00:00:00') AS DATETIME)");
+        assert_eq!(render(Flavor::Oracle, month()), "TRUNC(created_at, 'MM')");
+        assert_eq!(render(Flavor::BigQuery, month()), "TIMESTAMP_TRUNC(created_at, MONTH)");
+
+        let later = || date_add(Expr::col("created_at"), -3, DateUnit::Day);
+        assert_eq!(render(Flavor::MySQL, later()), "DATE_ADD(created_at, INTERVAL -3 DAY)");
+        assert_eq!(render(Flavor::PostgreSQL, later().lt(now())), "created_at + INTERVAL '-3 day' < NOW()");
+        assert_eq!(render(Flavor::SQLServer, later()), "DATEADD(day, -3, created_at)");
+        assert_eq!(render(Flavor::SQLite, later()), "datetime(created_at, '-3 day')");
+        assert_eq!(render(Flavor::Oracle, later()), "created_at + NUMTODSINTERVAL(-3, 'DAY')");
+
+        let tags = || string_agg(Expr::col("tag"), "','");
+        assert_eq!(render(Flavor::MySQL, tags()), r"GROUP_CONCAT(tag SEPARATOR ''',''')");
+        assert_eq!(render(Flavor::PostgreSQL, tags()), "STRING_AGG(tag, ''',''')");
+        assert_eq!(render(Flavor::Oracle, tags()), "LISTAGG(tag, ''',''') WITHIN GROUP (ORDER BY NULL)");
+        assert_eq!(render(Flavor::ClickHouse, tags()), "arrayStringConcat(groupArray(tag), ''',''')");
+
+        let names = || string_agg(Expr::col("first") + Expr::col("last"), ",");
+        assert_eq!(render(Flavor::MySQL, names()), "GROUP_CONCAT(first + last SEPARATOR ',')");
+        assert_eq!(
+            render(Flavor::Oracle, names().binary(BinaryOp::Concat, Expr::raw("'.'"))),
+            "LISTAGG(first + last, ',') WITHIN GROUP (ORDER BY NULL) || '.'"
+        );
+    }
+
+    #[test]
+    fn test_date_feature() {
+        assert_eq!(render(Flavor::CQL, date_trunc(DateUnit::Day, Expr::col("t"))), "DATE_TRUNC('day', t)");
+        assert_eq!(render(Flavor::CQL, date_add(Expr::col("t"), 1, DateUnit::Hour)), "t + INTERVAL '1 hour'");
+
+        let mut args = Args::new();
+        args.mode = BuildMode::Strict;
+        let format = args.add_expr(date_trunc(DateUnit::Day, Expr::col("t")));
+        let err = args.compile_with_flavor(&format, Flavor::CQL, Vec::new()).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedFeature { feature: Feature::DateTrunc, .. }));
+
+        let format = args.add_expr(date_add(Expr::col("t"), 1, DateUnit::Hour));
+        let err = args.compile_with_flavor(&format, Flavor::CQL, Vec::new()).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedFeature { feature: Feature::DateAdd, .. }));
+    }
+}
@@ -0,0 +1,243 @@
+use std::fmt::Write;
+
+use crate::array;
+use crate::expr::{BinaryOp, Expr};
+use crate::flavor::Flavor;
+use crate::value::Value;
+
+// 按方言改写较长的 IN 和 NOT IN 列表，不需要改写时返回 None
+// 1. PostgreSQL 将整个列表绑定为一个数组参数，见 array::rewrite_in_list
+// 2. pack 为 true 时，将多于一个值的列表编码为一个 JSON 参数，再用表函数展开：
+//    SQL Server 的 OPENJSON、SQLite 的 json_each、MySQL 和 MariaDB 的 JSON_TABLE
+//    语句的参数总数超过方言的上限时，Args::compile_with_flavor 会以 pack 为 true 重新编译
+// 3. 值的数量超过 IN 列表的上限时（例如 Oracle 的 1000），拆分为多个用 OR 连接的 IN 列表
+// 只改写全部由参数值组成的列表，包含列或子查询时保持原样
+pub(crate) fn rewrite(flavor: &Flavor, pack: bool, op: BinaryOp, left: &Expr, values: &[Expr]) -> Option<Expr> {
+    if let Some(any) = array::rewrite_in_list(flavor, op, left, values) {
+        return Some(any);
+    }
+
+    if pack && values.len() > 1 {
+        if let Some(table) = table_expr(flavor, values) {
+            return Some(left.clone().binary(op, Expr::List(vec![table])));
+        }
+    }
+
+    let max = flavor.limits().max_in_list.filter(|max| values.len() > *max)?;
+    let chunks = values.chunks(max).map(|chunk| left.clone().binary(op, Expr::List(chunk.to_vec())));
+
+    // NOT IN 拆分后需要同时不在每一组中
+    let combined = match op {
+        BinaryOp::NotIn => chunks.reduce(Expr::and),
+        _ => chunks.reduce(Expr::or),
+    };
+
+    // 结果可能与外层的字符串直接拼接，需要加上括号
+    combined.map(|expr| Expr::List(vec![expr]))
+}
+
+// 返回从 JSON 数组参数中展开值的子查询，方言没有对应的表函数或值无法编码为 JSON 时返回 None
+fn table_expr(flavor: &Flavor, values: &[Expr]) -> Option<Expr> {
+    let values = values
+        .iter()
+        .map(|v| match v {
+            Expr::Value(v) => Some(v),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let json = Expr::Value(Value::String(json_array(&values)?));
+
+    let (column, name, args, alias) = match flavor {
+        Flavor::SQLServer => ("value", "OPENJSON", vec![json], None),
+        Flavor::SQLite => ("value", "json_each", vec![json], None),
+        // JSON_TABLE 从 MySQL 8.0 开始提供，MySQL 5.7 不改写
+        Flavor::MySQL | Flavor::MariaDB => {
+            let columns = format!("'$[*]' COLUMNS (v {} PATH '$')", mysql_column_type(&values));
+            ("v", "JSON_TABLE", vec![json, Expr::Raw(columns)], Some("in_list".to_string()))
+        }
+        _ => return None,
+    };
+
+    Some(Expr::TableFunc {
+        column: column.to_string(),
+        name: name.to_string(),
+        args,
+        alias,
+    })
+}
+
+// JSON_TABLE 的列必须声明类型：全是整数时为 BIGINT，有浮点数时为 DOUBLE，其他情况为足够长的 VARCHAR
+fn mysql_column_type(values: &[&Value]) -> String {
+    let is_integer = |v: &Value| {
+        matches!(
+            v,
+            Value::TinyInt(_)
+                | Value::SmallInt(_)
+                | Value::Int(_)
+                | Value::BigInt(_)
+                | Value::TinyUnsigned(_)
+                | Value::SmallUnsigned(_)
+                | Value::Unsigned(_)
+                | Value::BigUnsigned(_)
+        )
+    };
+    let is_float = |v: &Value| matches!(v, Value::Float(_) | Value::Double(_));
+    let non_null = || values.iter().copied().filter(|v| !v.is_null());
+
+    if non_null().all(is_integer) {
+        return "BIGINT".to_string();
+    }
+
+    if non_null().all(|v| is_integer(v) || is_float(v)) {
+        return "DOUBLE".to_string();
+    }
+
+    let len = non_null()
+        .map(|v| match v {
+            Value::String(s) => s.chars().count(),
+            other => other.to_text().map_or(0, |text| text.chars().count()),
+        })
+        .max()
+        .unwrap_or(0);
+
+    format!("VARCHAR({})", len.max(1))
+}
+
+// 将值编码为 JSON 数组，字节串、数组和非有限的浮点数无法编码时返回 None
+// 日期时间、UUID 等扩展类型编码为它们的文本形式
+fn json_array(values: &[&Value]) -> Option<String> {
+    let mut buf = String::from("[");
+
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            buf.push(',');
+        }
+
+        let _ = match value {
+            Value::Null => write!(buf, "null"),
+            Value::Bool(v) => write!(buf, "{}", v),
+            Value::TinyInt(v) => write!(buf, "{}", v),
+            Value::SmallInt(v) => write!(buf, "{}", v),
+            Value::Int(v) => write!(buf, "{}", v),
+            Value::BigInt(v) => write!(buf, "{}", v),
+            Value::TinyUnsigned(v) => write!(buf, "{}", v),
+            Value::SmallUnsigned(v) => write!(buf, "{}", v),
+            Value::Unsigned(v) => write!(buf, "{}", v),
+            Value::BigUnsigned(v) => write!(buf, "{}", v),
+            Value::Float(v) if v.is_finite() => write!(buf, "{}", v),
+            Value::Double(v) if v.is_finite() => write!(buf, "{}", v),
+            Value::String(v) => {
+                json_string(&mut buf, v);
+                Ok(())
+            }
+            other => {
+                json_string(&mut buf, &other.to_text()?);
+                Ok(())
+            }
+        };
+    }
+
+    buf.push(']');
+    Some(buf)
+}
+
+fn json_string(buf: &mut String, s: &str) {
+    buf.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(buf, "\\u{:04x}", c as u32);
+            }
+            c => buf.push(c),
+        }
+    }
+
+    buf.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::Args;
+    use crate::cond::Cond;
+    use crate::error::Error;
+
+    #[test]
+    fn test_in_list() {
+        // 三个 800 个值的列表合计超过 SQL Server 的 2100 个参数，全部编码为 JSON 参数
+        let mut args = Args::new();
+        let mut cond = Cond::new(&mut args);
+        let lists = [cond.in_("a", 0..800), cond.in_("b", 0..800), cond.not_in("c", 0..800)];
+        let names = cond.not_in("name", ["a\"b", "c"]);
+        let single = cond.in_("d", [1]);
+        let format = cond.and(&[&lists[..], &[names, single]].concat());
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::SQLServer, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            "(a IN (SELECT value FROM OPENJSON(@p1)) AND b IN (SELECT value FROM OPENJSON(@p2)) \
+             AND c NOT IN (SELECT value FROM OPENJSON(@p3)) AND name NOT IN (SELECT value FROM OPENJSON(@p4)) AND d IN (@p5))"
+        );
+        assert_eq!(values.len(), 5);
+        assert!(matches!(&values[0], Value::String(json) if json.starts_with("[0,1,2,") && json.ends_with(",799]")));
+        assert_eq!(values[3], Value::String(r#"["a\"b","c"]"#.to_string()));
+
+        // 参数总数没有超过上限时保持原样
+        let mut args = Args::new();
+        let mut cond = Cond::new(&mut args);
+        let format = cond.in_("id", 0..1100);
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::SQLServer, Vec::new()).unwrap();
+        assert!(sql.starts_with("id IN (@p1, @p2, "));
+        assert_eq!(values.len(), 1100);
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::Oracle, Vec::new()).unwrap();
+        assert!(sql.starts_with("(id IN (:1, :2,"));
+        assert!(sql.contains(":1000) OR id IN (:1001, "));
+        assert_eq!(values.len(), 1100);
+
+        let mut args = Args::new();
+        let mut cond = Cond::new(&mut args);
+        let format = cond.not_in("id", 0..2500);
+
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::Oracle, Vec::new()).unwrap();
+        assert_eq!(sql.matches("NOT IN").count(), 3);
+        assert_eq!(sql.matches(" AND ").count(), 2);
+
+        let mut args = Args::new();
+        let mut cond = Cond::new(&mut args);
+        let format = cond.in_("name", (0..40000).map(|i| format!("n{}", i)));
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::SQLite, Vec::new()).unwrap();
+        assert_eq!(sql, "name IN (SELECT value FROM json_each(?))");
+        assert_eq!(values.len(), 1);
+
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::MySQL, Vec::new()).unwrap();
+        assert!(sql.starts_with("name IN (?, ?, "));
+
+        let mut args = Args::new();
+        let mut cond = Cond::new(&mut args);
+        let format = cond.in_("id", 0..70000);
+
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::MySQL, Vec::new()).unwrap();
+        assert_eq!(sql, "id IN (SELECT v FROM JSON_TABLE(?, '$[*]' COLUMNS (v BIGINT PATH '$')) AS in_list)");
+
+        let err = args.compile_with_flavor(&format, Flavor::MySQL57, Vec::new()).unwrap_err();
+        assert!(matches!(err, Error::TooManyArgs { count: 70000, max: 65535 }));
+
+        // 没有可以改写的 IN 列表时仍然返回 TooManyArgs
+        let mut args = Args::new();
+        let format = (0..2101).map(|i| args.add(i)).collect::<Vec<_>>().join(", ");
+        let err = args.compile_with_flavor(&format, Flavor::SQLServer, Vec::new()).unwrap_err();
+        assert!(matches!(err, Error::TooManyArgs { count: 2101, max: 2100 }));
+
+        let values = [Value::String("a\"b\\".to_string()), Value::Null, Value::Double(1.5)];
+        assert_eq!(json_array(&values.iter().collect::<Vec<_>>()).unwrap(), r#"["a\"b\\",null,1.5]"#);
+    }
+}
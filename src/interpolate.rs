@@ -1,315 +1,269 @@
-use std::fmt;
-use std::str;
+use std::fmt::Write;
+use crate::dialect::{self, Capabilities, Dialect, PlaceholderStyle};
 use crate::error::Error;
+use crate::flavor::Flavor;
+use crate::lexer::{Lexer, TokenKind};
+use crate::value::Value;
 
-// 定义 Flavor 枚举，表示不同的 SQL 方言
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum Flavor {
-    MySQL,
-    PostgreSQL,
-    SQLite,
-    SQLServer,
-    CQL,
-    ClickHouse,
-    Presto,
-    Oracle,
-    Informix,
+// 占位符编码函数，参数为输出缓冲区和参数下标
+type Encoder<'a> = dyn FnMut(&mut String, usize) -> Result<(), Error> + 'a;
+
+// 定义 mysql_interpolate 函数
+pub fn mysql_interpolate(query: &str, args: &[Value]) -> Result<String, Error> {
+    interpolate(&dialect::MYSQL, query, args)
 }
 
+// 定义 postgresql_interpolate 函数
+pub fn postgresql_interpolate(query: &str, args: &[Value]) -> Result<String, Error> {
+    interpolate(&dialect::POSTGRESQL, query, args)
+}
 
+// 定义 sqlite_interpolate 函数
+pub fn sqlite_interpolate(query: &str, args: &[Value]) -> Result<String, Error> {
+    interpolate(&dialect::SQLITE, query, args)
+}
 
-// 实现 std::error::Error 以便错误可以集成到 Rust 的错误处理系统
+// 定义 sqlserver_interpolate 函数
+pub fn sqlserver_interpolate(query: &str, args: &[Value]) -> Result<String, Error> {
+    interpolate(&dialect::SQLSERVER, query, args)
+}
 
-// 定义 mysql_interpolate 函数
-pub fn mysql_interpolate(query: &str, args: &[&dyn fmt::Display]) -> Result<String, Error> {
-    mysql_like_interpolate(Flavor::MySQL, query, args)
+// 定义 cql_interpolate 函数
+pub fn cql_interpolate(query: &str, args: &[Value]) -> Result<String, Error> {
+    interpolate(&dialect::CQL, query, args)
 }
 
-// 定义 mysql_like_interpolate 函数
-pub fn mysql_like_interpolate(flavor: Flavor, query: &str, args: &[&dyn fmt::Display]) -> Result<String, Error> {
-    let mut buf = String::with_capacity(query.len() + args.len() * 20);
-    let mut cnt = 0;
-    let max = args.len();
-    let mut escaping = false;
-    let mut quote = None;
-    let mut chars = query.chars().peekable();
-
-    while let Some(r) = chars.next() {
-        if escaping {
-            escaping = false;
-            buf.push(r);
-            continue;
-        }
+// 定义 clickhouse_interpolate 函数
+pub fn clickhouse_interpolate(query: &str, args: &[Value]) -> Result<String, Error> {
+    interpolate(&dialect::CLICKHOUSE, query, args)
+}
 
-        match r {
-            '?' => {
-                if quote.is_none() {
-                    if cnt >= max {
-                        return Err(Error::MissingArgs);
-                    }
-                    encode_value(&mut buf, args[cnt], flavor)?;
-                    cnt += 1;
-                } else {
-                    buf.push(r);
-                }
-            }
-            '\'' | '"' | '`' => {
-                if quote == Some(r) {
-                    quote = None;
-                } else if quote.is_none() {
-                    quote = Some(r);
-                }
-                buf.push(r);
-            }
-            '\\' => {
-                if quote.is_some() {
-                    escaping = true;
-                }
-                buf.push(r);
-            }
-            _ => buf.push(r),
-        }
-    }
+// 定义 presto_interpolate 函数
+pub fn presto_interpolate(query: &str, args: &[Value]) -> Result<String, Error> {
+    interpolate(&dialect::PRESTO, query, args)
+}
 
-    Ok(buf)
+// 定义 oracle_interpolate 函数
+pub fn oracle_interpolate(query: &str, args: &[Value]) -> Result<String, Error> {
+    interpolate(&dialect::ORACLE, query, args)
 }
 
-// 定义 encode_value 函数
-fn encode_value(buf: &mut String, arg: &dyn fmt::Display, flavor: Flavor) -> Result<(), Error> {
-    match flavor {
-        Flavor::MySQL | Flavor::SQLite | Flavor::CQL | Flavor::ClickHouse | Flavor::Presto | Flavor::Informix => {
-            write!(buf, "{}", arg).map_err(|_| Error::InvalidUtf8)
-        }
-        Flavor::PostgreSQL => {
-            write!(buf, "${}", arg).map_err(|_| Error::InvalidUtf8)
-        }
-        Flavor::SQLServer => {
-            write!(buf, "@p{}", arg).map_err(|_| Error::InvalidUtf8)
-        }
-        Flavor::Oracle => {
-            write!(buf, ":{}", arg).map_err(|_| Error::InvalidUtf8)
-        }
-    }
+// 定义 informix_interpolate 函数
+pub fn informix_interpolate(query: &str, args: &[Value]) -> Result<String, Error> {
+    interpolate(&dialect::INFORMIX, query, args)
 }
 
-// 定义 postgresql_interpolate 函数
-pub fn postgresql_interpolate(query: &str, args: &[&dyn fmt::Display]) -> Result<String, Error> {
-    let mut buf = String::with_capacity(query.len() + args.len() * 20);
+// 使用指定方言将参数内联到 SQL 中
+pub fn interpolate(dialect: &dyn Dialect, query: &str, args: &[Value]) -> Result<String, Error> {
+    interpolate_with(dialect, query, args.len(), &mut |buf, i| {
+        dialect.encode_literal(buf, &args[i]).map_err(|err| err.at_index(i))
+    })
+}
+
+// 使用自定义编码函数替换指定方言的占位符
+// 只替换代码中的占位符，字符串、加引号的标识符和注释中的内容原样保留
+pub(crate) fn interpolate_with(dialect: &dyn Dialect, query: &str, max: usize, encode: &mut Encoder) -> Result<String, Error> {
+    let mut buf = String::with_capacity(query.len() + max * 20);
     let mut cnt = 0;
-    let max = args.len();
-    let mut escaping = false;
-    let mut quote = None;
-    let mut dollar_quote = None;
-    let mut chars = query.chars().peekable();
-
-    while let Some(r) = chars.next() {
-        if escaping {
-            escaping = false;
-            buf.push(r);
+    let mut offset = 0;
+
+    for token in Lexer::new(query, dialect.capabilities()) {
+        let at = offset;
+        offset += token.text.len();
+
+        if token.kind != TokenKind::Code {
+            buf.push_str(token.text);
             continue;
         }
 
-        match r {
-            '$' => {
-                if quote.is_none() {
-                    let mut placeholder = String::new();
-                    while let Some(&c) = chars.peek() {
-                        if c.is_ascii_digit() {
-                            placeholder.push(c);
-                            chars.next();
-                        } else {
-                            break;
-                        }
-                    }
-                    if !placeholder.is_empty() {
-                        let idx: usize = placeholder.parse().map_err(|_| Error::UnsupportedArgs)?;
-                        if idx > max {
-                            return Err(Error::MissingArgs);
-                        }
-                        encode_value(&mut buf, args[idx - 1], Flavor::PostgreSQL)?;
-                    } else {
-                        buf.push(r);
-                    }
-                } else {
-                    buf.push(r);
-                }
-            }
-            '\'' | '"' => {
-                if quote == Some(r) {
-                    quote = None;
-                } else if quote.is_none() {
-                    quote = Some(r);
-                }
-                buf.push(r);
-            }
-            '\\' => {
-                if quote.is_some() {
-                    escaping = true;
-                }
-                buf.push(r);
-            }
-            _ => buf.push(r),
+        match dialect.placeholder_style() {
+            PlaceholderStyle::Question => replace_question(&mut buf, token.text, at, &mut cnt, max, encode)?,
+            PlaceholderStyle::Numbered(prefix) => replace_numbered(&mut buf, token.text, at, prefix, max, encode)?,
         }
     }
 
     Ok(buf)
 }
 
-// 定义 sqlserver_interpolate 函数
-pub fn sqlserver_interpolate(query: &str, args: &[&dyn fmt::Display]) -> Result<String, Error> {
-    let mut buf = String::with_capacity(query.len() + args.len() * 20);
-    let mut cnt = 0;
-    let max = args.len();
-    let mut escaping = false;
-    let mut quote = None;
-    let mut chars = query.chars().peekable();
-
-    while let Some(r) = chars.next() {
-        if escaping {
-            escaping = false;
+// 在一段代码中按顺序替换 ? 占位符，offset 为这段代码在 SQL 中的位置
+fn replace_question(
+    buf: &mut String,
+    code: &str,
+    offset: usize,
+    cnt: &mut usize,
+    max: usize,
+    encode: &mut Encoder,
+) -> Result<(), Error> {
+    for (pos, r) in code.char_indices() {
+        if r != '?' {
             buf.push(r);
             continue;
         }
 
-        match r {
-            '@' => {
-                if quote.is_none() {
-                    let mut placeholder = String::new();
-                    while let Some(&c) = chars.peek() {
-                        if c.is_ascii_digit() {
-                            placeholder.push(c);
-                            chars.next();
-                        } else {
-                            break;
-                        }
-                    }
-                    if !placeholder.is_empty() {
-                        let idx: usize = placeholder.parse().map_err(|_| Error::UnsupportedArgs)?;
-                        if idx > max {
-                            return Err(Error::MissingArgs);
-                        }
-                        encode_value(&mut buf, args[idx - 1], Flavor::SQLServer)?;
-                    } else {
-                        buf.push(r);
-                    }
-                } else {
-                    buf.push(r);
-                }
-            }
-            '\'' | '"' => {
-                if quote == Some(r) {
-                    quote = None;
-                } else if quote.is_none() {
-                    quote = Some(r);
-                }
-                buf.push(r);
-            }
-            '\\' => {
-                if quote.is_some() {
-                    escaping = true;
-                }
-                buf.push(r);
-            }
-            _ => buf.push(r),
+        if *cnt >= max {
+            return Err(Error::MissingArgs {
+                expected: *cnt + 1,
+                got: max,
+                at: offset + pos,
+            });
         }
+
+        encode(buf, *cnt)?;
+        *cnt += 1;
     }
 
-    Ok(buf)
+    Ok(())
 }
 
-// 定义 oracle_interpolate 函数
-pub fn oracle_interpolate(query: &str, args: &[&dyn fmt::Display]) -> Result<String, Error> {
-    let mut buf = String::with_capacity(query.len() + args.len() * 20);
-    let mut cnt = 0;
-    let max = args.len();
-    let mut escaping = false;
-    let mut quote = None;
-    let mut dollar_quote = None;
-    let mut chars = query.chars().peekable();
-
-    while let Some(r) = chars.next() {
-        if escaping {
-            escaping = false;
-            buf.push(r);
+// 在一段代码中替换编号占位符，offset 为这段代码在 SQL 中的位置
+fn replace_numbered(
+    buf: &mut String,
+    code: &str,
+    offset: usize,
+    prefix: &str,
+    max: usize,
+    encode: &mut Encoder,
+) -> Result<(), Error> {
+    let mut rest = code;
+
+    while let Some(pos) = rest.find(prefix) {
+        buf.push_str(&rest[..pos]);
+        let after = &rest[pos + prefix.len()..];
+
+        // 连续两个前缀不是占位符，例如 :: 类型转换
+        if let Some(next) = after.strip_prefix(prefix) {
+            buf.push_str(prefix);
+            buf.push_str(prefix);
+            rest = next;
             continue;
         }
 
-        match r {
-            ':' => {
-                if quote.is_none() {
-                    let mut placeholder = String::new();
-                    while let Some(&c) = chars.peek() {
-                        if c.is_ascii_digit() {
-                            placeholder.push(c);
-                            chars.next();
-                        } else {
-                            break;
-                        }
-                    }
-                    if !placeholder.is_empty() {
-                        let idx: usize = placeholder.parse().map_err(|_| Error::UnsupportedArgs)?;
-                        if idx > max {
-                            return Err(Error::MissingArgs);
-                        }
-                        encode_value(&mut buf, args[idx - 1], Flavor::Oracle)?;
-                    } else {
-                        buf.push(r);
-                    }
-                } else {
-                    buf.push(r);
-                }
-            }
-            '\'' | '"' => {
-                if quote == Some(r) {
-                    quote = None;
-                } else if quote.is_none() {
-                    quote = Some(r);
-                }
-                buf.push(r);
-            }
-            '\\' => {
-                if quote.is_some() {
-                    escaping = true;
-                }
-                buf.push(r);
-            }
-            _ => buf.push(r),
+        let digits = after.len() - after.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+
+        if digits == 0 {
+            buf.push_str(prefix);
+            rest = after;
+            continue;
         }
+
+        // 超出 usize 范围的编号一定没有对应的参数
+        let idx: usize = after[..digits].parse().unwrap_or(usize::MAX);
+
+        if idx == 0 || idx > max {
+            return Err(Error::MissingArgs {
+                expected: idx,
+                got: max,
+                at: offset + code.len() - rest.len() + pos,
+            });
+        }
+
+        encode(buf, idx - 1)?;
+        rest = &after[digits..];
     }
 
-    Ok(buf)
+    buf.push_str(rest);
+    Ok(())
 }
 
+// 定义 encode_value 函数
+// 将参数编码为 SQL 字面量，无法安全编码的类型返回 UnsupportedArgs，下标由调用方补充
+// 日期时间、UUID 和 JSON 编码为字符串，Decimal 编码为数字
+pub(crate) fn encode_value(buf: &mut String, arg: &Value, caps: &Capabilities) -> Result<(), Error> {
+    let result = match arg {
+        Value::Null => write!(buf, "NULL"),
+        Value::Bool(v) => match (caps.numeric_booleans, *v) {
+            (true, true) => write!(buf, "1"),
+            (true, false) => write!(buf, "0"),
+            (false, true) => write!(buf, "TRUE"),
+            (false, false) => write!(buf, "FALSE"),
+        },
+        Value::TinyInt(v) => write!(buf, "{}", v),
+        Value::SmallInt(v) => write!(buf, "{}", v),
+        Value::Int(v) => write!(buf, "{}", v),
+        Value::BigInt(v) => write!(buf, "{}", v),
+        Value::TinyUnsigned(v) => write!(buf, "{}", v),
+        Value::SmallUnsigned(v) => write!(buf, "{}", v),
+        Value::Unsigned(v) => write!(buf, "{}", v),
+        Value::BigUnsigned(v) => write!(buf, "{}", v),
+        // NaN 和无穷大没有通用的字面量，直接写出会变成标识符
+        Value::Float(v) if v.is_finite() => write!(buf, "{}", v),
+        Value::Double(v) if v.is_finite() => write!(buf, "{}", v),
+        Value::Float(_) | Value::Double(_) => return Err(Error::UnsupportedArgs { index: 0, kind: arg.kind() }),
+        Value::String(v) => {
+            quote_string(buf, v, caps);
+            Ok(())
+        }
+        Value::Bytes(_) => return Err(Error::UnsupportedArgs { index: 0, kind: arg.kind() }),
+        #[cfg(feature = "rust_decimal")]
+        Value::Decimal(v) => write!(buf, "{}", v),
+        #[allow(unreachable_patterns)]
+        other => match other.to_text() {
+            Some(text) => {
+                quote_string(buf, &text, caps);
+                Ok(())
+            }
+            None => return Err(Error::UnsupportedArgs { index: 0, kind: other.kind() }),
+        },
+    };
 
-// 定义 sqlite_interpolate 函数
-pub fn sqlite_interpolate(query: &str, args: &[&dyn fmt::Display]) -> Result<String, Error> {
-    mysql_like_interpolate(Flavor::SQLite, query, args)
+    result.map_err(|_| Error::InvalidUtf8)
 }
 
-// 定义 cql_interpolate 函数
-pub fn cql_interpolate(query: &str, args: &[&dyn fmt::Display]) -> Result<String, Error> {
-    mysql_like_interpolate(Flavor::CQL, query, args)
-}
+// 将字符串编码为带引号的 SQL 字面量
+fn quote_string(buf: &mut String, s: &str, caps: &Capabilities) {
+    let backslash_escape = caps.backslash_escape;
 
-// 定义 clickhouse_interpolate 函数
-pub fn clickhouse_interpolate(query: &str, args: &[&dyn fmt::Display]) -> Result<String, Error> {
-    mysql_like_interpolate(Flavor::ClickHouse, query, args)
-}
+    if caps.national_strings {
+        buf.push('N');
+    }
 
-// 定义 presto_interpolate 函数
-pub fn presto_interpolate(query: &str, args: &[&dyn fmt::Display]) -> Result<String, Err> {
-    mysql_like_interpolate(Flavor::Presto, query, args)
+    buf.push('\'');
+
+    for c in s.chars() {
+        match c {
+            '\'' => buf.push_str("''"),
+            '\\' if backslash_escape => buf.push_str("\\\\"),
+            '\0' if backslash_escape => buf.push_str("\\0"),
+            '\n' if backslash_escape => buf.push_str("\\n"),
+            '\r' if backslash_escape => buf.push_str("\\r"),
+            _ => buf.push(c),
+        }
+    }
+
+    buf.push('\'');
 }
 
-// 定义 informix_interpolate 函数
-pub fn informix_interpolate(query: &str, args: &[&dyn fmt::Display]) -> Result<String, Err> {
-    mysql_like_interpolate(Flavor::Informix, query, args)
+// 生成用于调试的 SQL
+// 优先将参数内联到 SQL 中，若有参数无法安全编码，则保留占位符并在其后以注释标注参数值
+pub fn debug_sql(flavor: &Flavor, sql: &str, args: &[Value]) -> String {
+    let dialect = flavor.dialect();
+
+    match interpolate(dialect, sql, args) {
+        Ok(s) => return s,
+        Err(Error::UnsupportedArgs { .. }) => {}
+        Err(_) => return sql.to_string(),
+    }
+
+    let annotated = interpolate_with(dialect, sql, args.len(), &mut |buf, i| {
+        let mut value = String::new();
+
+        if dialect.encode_literal(&mut value, &args[i]).is_err() {
+            value = "<unsupported>".to_string();
+        }
+
+        // 参数值中的 */ 会提前结束注释，PostgreSQL 的注释可以嵌套，/* 也需要拆开
+        let value = value.replace("*/", "* /").replace("/*", "/ *");
+
+        write!(buf, "{} /* ?={} */", dialect.placeholder(i + 1), value).map_err(|_| Error::InvalidUtf8)
+    });
+
+    annotated.unwrap_or_else(|_| sql.to_string())
 }
 
 // // 示例用法
 // fn main() {
 //     let query = "SELECT * FROM users WHERE id = ? AND name = ?";
-//     let args: Vec<&dyn fmt::Display> = vec![&1, &"Alice"];
+//     let args = vec![1.to_sql_value(), "Alice".to_sql_value()];
 //
 //     match sqlite_interpolate(query, &args) {
 //         Ok(sql) => println!("{}", sql),
@@ -0,0 +1,357 @@
+use crate::builder::BuildMode;
+use crate::dialect::Feature;
+use crate::error::Error;
+use crate::expr::{BinaryOp, Expr};
+use crate::flavor::Flavor;
+use crate::func::{call, clause, literal, Function};
+use crate::value::Value;
+
+// JSON 路径中的一段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    // .key 或 ."key"
+    Key(String),
+    // [n]，从 0 开始
+    Index(u32),
+}
+
+// 解析 $.a.b[0] 形式的 JSON 路径
+// 包含特殊字符的键名需要用双引号括起来，例如 $."a.b"，其中的 " 和 \ 用 \ 转义
+pub fn parse_path(path: &str) -> Result<Vec<PathSegment>, Error> {
+    let invalid = || Error::InvalidJsonPath { path: path.to_string() };
+    let rest = path.strip_prefix('$').ok_or_else(invalid)?;
+    let mut chars = rest.chars().peekable();
+    let mut segments = Vec::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '.' if chars.peek() == Some(&'"') => {
+                chars.next();
+                let mut key = String::new();
+
+                loop {
+                    match chars.next().ok_or_else(invalid)? {
+                        '"' => break,
+                        '\\' => key.push(chars.next().ok_or_else(invalid)?),
+                        c => key.push(c),
+                    }
+                }
+
+                segments.push(PathSegment::Key(key));
+            }
+            '.' => {
+                let mut key = String::new();
+
+                while let Some(c) = chars.next_if(|&c| c.is_alphanumeric() || c == '_') {
+                    key.push(c);
+                }
+
+                if key.is_empty() {
+                    return Err(invalid());
+                }
+
+                segments.push(PathSegment::Key(key));
+            }
+            '[' => {
+                let mut index = String::new();
+
+                while let Some(c) = chars.next_if(char::is_ascii_digit) {
+                    index.push(c);
+                }
+
+                if chars.next() != Some(']') {
+                    return Err(invalid());
+                }
+
+                segments.push(PathSegment::Index(index.parse().map_err(|_| invalid())?));
+            }
+            _ => return Err(invalid()),
+        }
+    }
+
+    Ok(segments)
+}
+
+// 将路径格式化为 $.a."b c"[0] 的形式，只包含字母、数字和下划线的键名不加引号
+pub fn format_path(segments: &[PathSegment]) -> String {
+    let mut path = String::from("$");
+
+    for segment in segments {
+        match segment {
+            PathSegment::Key(key) if !key.is_empty() && key.chars().all(|c| c.is_alphanumeric() || c == '_') => {
+                path.push('.');
+                path.push_str(key);
+            }
+            PathSegment::Key(key) => {
+                path.push_str(".\"");
+                path.push_str(&key.replace('\\', "\\\\").replace('"', "\\\""));
+                path.push('"');
+            }
+            PathSegment::Index(index) => path.push_str(&format!("[{}]", index)),
+        }
+    }
+
+    path
+}
+
+// 返回 path 指向的值，字符串不带引号：->>、JSON_EXTRACT、json_extract 或 JSON_VALUE
+// path 在编译时解析，格式不正确时返回 InvalidJsonPath
+// PostgreSQL 中每一段路径分别绑定为参数，列需要是 jsonb 类型
+pub fn json_extract(expr: impl Into<Expr>, path: &str) -> Expr {
+    call(Function::JsonExtract(path.to_string()), vec![expr.into()])
+}
+
+// 判断 JSON 文档是否包含 candidate：@>、JSON_CONTAINS 或 json_contains
+// candidate 是 JSON 文本，绑定为参数
+// SQLite 和 SQL Server 用表函数展开两侧，只判断 candidate 的每个元素是否出现在文档的顶层，
+// SQL Server 的 candidate 必须是数组或对象；其他方言按 BuildMode 处理
+pub fn json_contains(expr: impl Into<Expr>, candidate: impl Into<Expr>) -> Expr {
+    call(Function::JsonContains, vec![expr.into(), candidate.into()])
+}
+
+// 判断 JSON 对象是否包含顶层的键 key：?、JSON_CONTAINS_PATH、JSON_EXISTS 等，key 绑定为参数
+pub fn json_has_key(expr: impl Into<Expr>, key: &str) -> Expr {
+    call(Function::JsonHasKey(key.to_string()), vec![expr.into()])
+}
+
+// 返回 JSON 数组的长度，不支持的方言按 BuildMode 处理
+pub fn json_array_length(expr: impl Into<Expr>) -> Expr {
+    call(Function::JsonArrayLength, vec![expr.into()])
+}
+
+// 路径绑定为参数，Oracle 的路径必须是字面量，使用格式化后的路径内联
+fn path_arg(flavor: &Flavor, segments: &[PathSegment]) -> Result<Expr, Error> {
+    let path = format_path(segments);
+
+    if let Flavor::Oracle = flavor {
+        return literal(flavor, &path);
+    }
+
+    Ok(Expr::Value(Value::String(path)))
+}
+
+pub(crate) fn lower_extract(flavor: &Flavor, expr: Expr, path: &str) -> Result<Expr, Error> {
+    let segments = parse_path(path)?;
+
+    let extracted = match flavor {
+        Flavor::PostgreSQL => {
+            let last = segments.len().saturating_sub(1);
+
+            if segments.is_empty() {
+                return Ok(expr.binary(BinaryOp::Other("#>>"), Expr::raw("'{}'")));
+            }
+
+            segments.into_iter().enumerate().try_fold(expr, |acc, (i, segment)| {
+                let op = if i == last { "->>" } else { "->" };
+                // 参数会被推断为 text，下标必须写成 int 范围内的整数字面量才会按数组下标取值
+                let key = match segment {
+                    PathSegment::Key(key) => Expr::Value(Value::String(key)),
+                    PathSegment::Index(index) if i32::try_from(index).is_ok() => Expr::Raw(index.to_string()),
+                    PathSegment::Index(_) => return Err(Error::InvalidJsonPath { path: path.to_string() }),
+                };
+                Ok(acc.binary(BinaryOp::Other(op), key))
+            })?
+        }
+        Flavor::MySQL | Flavor::MySQL57 | Flavor::MariaDB | Flavor::Doris | Flavor::StarRocks => Expr::func(
+            "JSON_UNQUOTE",
+            [Expr::func("JSON_EXTRACT", [expr, path_arg(flavor, &segments)?])],
+        ),
+        Flavor::SQLite => Expr::func("json_extract", [expr, path_arg(flavor, &segments)?]),
+        Flavor::DuckDB => Expr::func("json_extract_string", [expr, path_arg(flavor, &segments)?]),
+        _ => Expr::func("JSON_VALUE", [expr, path_arg(flavor, &segments)?]),
+    };
+
+    Ok(extracted)
+}
+
+// 由片段拼成的子查询 SELECT columns FROM table [WHERE condition]，table 通常是带参数的表函数
+fn select(columns: &str, table: Expr, condition: Option<Expr>) -> Expr {
+    let from = clause(Expr::Raw(format!("SELECT {}", columns)), "FROM", table);
+
+    match condition {
+        Some(condition) => clause(from, "WHERE", condition),
+        None => from,
+    }
+}
+
+// candidate 展开后的每个元素都在 expr 展开后的值中：
+// NOT EXISTS (SELECT 1 FROM each(candidate) AS c WHERE c.value NOT IN (SELECT value FROM each(expr)))
+fn contains_each(each: &str, expr: Expr, candidate: Expr) -> Expr {
+    let values = select("value", Expr::func(each, [expr]), None);
+    let missing = Expr::raw("c.value").binary(BinaryOp::NotIn, Expr::List(vec![values]));
+    let candidates = clause(Expr::func(each, [candidate]), "AS", Expr::raw("c"));
+
+    !Expr::func("EXISTS", [select("1", candidates, Some(missing))])
+}
+
+pub(crate) fn lower_contains(flavor: &Flavor, mode: BuildMode, expr: Expr, candidate: Expr) -> Result<Expr, Error> {
+    if !flavor.check_feature(Feature::JsonContains, mode)? {
+        return Ok(Expr::func("JSON_CONTAINS", [expr, candidate]));
+    }
+
+    let contains = match flavor {
+        Flavor::PostgreSQL => expr.binary(BinaryOp::Other("@>"), candidate.cast("JSONB")),
+        Flavor::DuckDB => Expr::func("json_contains", [expr, candidate]),
+        Flavor::SQLite => contains_each("json_each", expr, candidate),
+        Flavor::SQLServer => contains_each("OPENJSON", expr, candidate),
+        _ => Expr::func("JSON_CONTAINS", [expr, candidate]),
+    };
+
+    Ok(contains)
+}
+
+pub(crate) fn lower_has_key(flavor: &Flavor, expr: Expr, key: &str) -> Result<Expr, Error> {
+    let segments = [PathSegment::Key(key.to_string())];
+
+    let exists = match flavor {
+        Flavor::PostgreSQL => expr.binary(BinaryOp::Other("?"), Expr::val(key)),
+        Flavor::MySQL | Flavor::MySQL57 | Flavor::MariaDB | Flavor::Doris | Flavor::StarRocks => Expr::func(
+            "JSON_CONTAINS_PATH",
+            [expr, Expr::raw("'one'"), path_arg(flavor, &segments)?],
+        ),
+        Flavor::SQLite => Expr::func("json_type", [expr, path_arg(flavor, &segments)?]).is_not_null(),
+        Flavor::SQLServer => Expr::func("JSON_PATH_EXISTS", [expr, path_arg(flavor, &segments)?]).eq(Expr::raw("1")),
+        Flavor::DuckDB => Expr::func("json_exists", [expr, path_arg(flavor, &segments)?]),
+        _ => Expr::func("JSON_EXISTS", [expr, path_arg(flavor, &segments)?]),
+    };
+
+    Ok(exists)
+}
+
+pub(crate) fn lower_array_length(flavor: &Flavor, mode: BuildMode, expr: Expr) -> Result<Expr, Error> {
+    if !flavor.check_feature(Feature::JsonArrayLength, mode)? {
+        return Ok(Expr::func("JSON_ARRAY_LENGTH", [expr]));
+    }
+
+    let length = match flavor {
+        Flavor::PostgreSQL => Expr::func("jsonb_array_length", [expr]),
+        Flavor::MySQL | Flavor::MySQL57 | Flavor::MariaDB | Flavor::Doris | Flavor::StarRocks => {
+            Expr::func("JSON_LENGTH", [expr])
+        }
+        Flavor::SQLServer => Expr::List(vec![select("COUNT(*)", Expr::func("OPENJSON", [expr]), None)]),
+        Flavor::Oracle => Expr::func("JSON_VALUE", [expr, Expr::raw("'$.size()'")]),
+        Flavor::ClickHouse => Expr::func("JSONLength", [expr]),
+        Flavor::Snowflake => Expr::func("ARRAY_SIZE", [Expr::func("PARSE_JSON", [expr])]),
+        Flavor::BigQuery => Expr::func("ARRAY_LENGTH", [Expr::func("JSON_QUERY_ARRAY", [expr])]),
+        _ => Expr::func("json_array_length", [expr]),
+    };
+
+    Ok(length)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::Args;
+    use crate::cond::Cond;
+
+    fn render(flavor: Flavor, expr: Expr) -> Result<(String, Vec<Value>), Error> {
+        let mut args = Args::new();
+        let format = args.add_expr(expr);
+        args.compile_with_flavor(&format, flavor, Vec::new())
+    }
+
+    #[test]
+    fn test_parse_path() {
+        let segments = parse_path(r#"$.a."b.c"[2].d_1"#).unwrap();
+        assert_eq!(
+            segments,
+            vec![
+                PathSegment::Key("a".to_string()),
+                PathSegment::Key("b.c".to_string()),
+                PathSegment::Index(2),
+                PathSegment::Key("d_1".to_string()),
+            ]
+        );
+        assert_eq!(format_path(&segments), r#"$.a."b.c"[2].d_1"#);
+        assert_eq!(parse_path("$").unwrap(), vec![]);
+
+        for path in ["a.b", "$.", "$.a[x]", "$.a[1", r#"$."a"#, "$.a b", "$.a'"] {
+            assert!(matches!(parse_path(path), Err(Error::InvalidJsonPath { .. })), "{}", path);
+        }
+    }
+
+    #[test]
+    fn test_json() {
+        let city = || json_extract(Expr::col("data"), "$.address.city").eq("Paris");
+
+        let (sql, values) = render(Flavor::PostgreSQL, city()).unwrap();
+        assert_eq!(sql, "data -> $1 ->> $2 = $3");
+        assert_eq!(values[0], Value::String("address".to_string()));
+
+        let (sql, values) = render(Flavor::PostgreSQL, json_extract(Expr::col("data"), "$.tags[2]")).unwrap();
+        assert_eq!(sql, "data -> $1 ->> 2");
+        assert_eq!(values, vec![Value::String("tags".to_string())]);
+
+        let err = render(Flavor::PostgreSQL, json_extract(Expr::col("data"), "$.tags[2147483648]")).unwrap_err();
+        assert!(matches!(err, Error::InvalidJsonPath { .. }));
+
+        let (sql, values) = render(Flavor::MySQL, city()).unwrap();
+        assert_eq!(sql, "JSON_UNQUOTE(JSON_EXTRACT(data, ?)) = ?");
+        assert_eq!(values[0], Value::String("$.address.city".to_string()));
+
+        assert_eq!(render(Flavor::SQLite, city()).unwrap().0, "json_extract(data, ?) = ?");
+        assert_eq!(render(Flavor::SQLServer, city()).unwrap().0, "JSON_VALUE(data, @p1) = @p2");
+        assert_eq!(render(Flavor::Oracle, city()).unwrap().0, "JSON_VALUE(data, '$.address.city') = :1");
+
+        let err = render(Flavor::MySQL, json_extract(Expr::col("data"), "$.a'--")).unwrap_err();
+        assert!(matches!(err, Error::InvalidJsonPath { .. }));
+
+        let mut args = Args::new();
+        let mut cond = Cond::new(&mut args);
+        let contains = cond.json_contains("tags", r#"["a"]"#);
+        let has_key = cond.json_has_key("data", "owner");
+        let format = cond.and(&[contains, has_key]);
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(sql, "(tags @> CAST($1 AS JSONB) AND data ? $2)");
+        assert_eq!(values[1], Value::String("owner".to_string()));
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::MySQL, Vec::new()).unwrap();
+        assert_eq!(sql, "(JSON_CONTAINS(tags, ?) AND JSON_CONTAINS_PATH(data, 'one', ?))");
+        assert_eq!(values[1], Value::String("$.owner".to_string()));
+
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::SQLite, Vec::new()).unwrap();
+        assert!(sql.ends_with("json_type(data, ?) IS NOT NULL)"));
+
+        assert_eq!(render(Flavor::PostgreSQL, json_array_length(Expr::col("tags"))).unwrap().0, "jsonb_array_length(tags)");
+        assert_eq!(render(Flavor::MySQL, json_array_length(Expr::col("tags"))).unwrap().0, "JSON_LENGTH(tags)");
+    }
+
+    #[test]
+    fn test_json_flavors() {
+        let contains = || json_contains(Expr::col("tags"), r#"["a"]"#);
+
+        assert_eq!(
+            render(Flavor::SQLite, contains()).unwrap().0,
+            "NOT EXISTS(SELECT 1 FROM json_each(?) AS c WHERE c.value NOT IN (SELECT value FROM json_each(tags)))"
+        );
+        assert_eq!(
+            render(Flavor::SQLServer, contains()).unwrap().0,
+            "NOT EXISTS(SELECT 1 FROM OPENJSON(@p1) AS c WHERE c.value NOT IN (SELECT value FROM OPENJSON(tags)))"
+        );
+
+        let length = || json_array_length(Expr::col("tags"));
+        assert_eq!(render(Flavor::SQLServer, length().gt(1)).unwrap().0, "(SELECT COUNT(*) FROM OPENJSON(tags)) > @p1");
+        assert_eq!(render(Flavor::ClickHouse, length()).unwrap().0, "JSONLength(tags)");
+        assert_eq!(render(Flavor::Snowflake, length()).unwrap().0, "ARRAY_SIZE(PARSE_JSON(tags))");
+        assert_eq!(render(Flavor::BigQuery, length()).unwrap().0, "ARRAY_LENGTH(JSON_QUERY_ARRAY(tags))");
+        assert_eq!(render(Flavor::Presto, length()).unwrap().0, "json_array_length(tags)");
+
+        // 不支持的方言在宽松模式下原样生成，严格模式返回 UnsupportedFeature
+        assert_eq!(render(Flavor::Oracle, contains()).unwrap().0, "JSON_CONTAINS(tags, :1)");
+
+        let mut args = Args::new();
+        args.mode = BuildMode::Strict;
+        let format = format!("{} AND {}", args.add_expr(contains()), args.add_expr(length().gt(0)));
+        let err = args.compile_with_flavor(&format, Flavor::Oracle, Vec::new()).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedFeature { feature: Feature::JsonContains, .. }));
+
+        let err = args.compile_with_flavor(&format, Flavor::Informix, Vec::new()).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedFeature { feature: Feature::JsonContains, .. }));
+
+        let format = args.add_expr(length());
+        let err = args.compile_with_flavor(&format, Flavor::CQL, Vec::new()).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedFeature { feature: Feature::JsonArrayLength, .. }));
+    }
+}
@@ -0,0 +1,141 @@
+// 各数据库方言的保留关键字列表（均为大写）
+// 这些列表用于判断标识符是否需要加引号，并非完整的语法关键字集合
+
+// SQL 标准中大多数数据库共有的保留关键字
+pub(crate) const COMMON: &[&str] = &[
+    "ADD", "ALL", "ALTER", "AND", "ANY", "AS", "ASC", "BETWEEN", "BY", "CASE", "CHECK", "COLUMN",
+    "CONSTRAINT", "CREATE", "CROSS", "CURRENT_DATE", "CURRENT_TIME", "CURRENT_TIMESTAMP",
+    "CURRENT_USER", "DEFAULT", "DELETE", "DESC", "DISTINCT", "DROP", "ELSE", "END", "EXISTS",
+    "FALSE", "FOR", "FOREIGN", "FROM", "FULL", "GRANT", "GROUP", "HAVING", "IN", "INNER",
+    "INSERT", "INTERSECT", "INTO", "IS", "JOIN", "LEFT", "LIKE", "NOT", "NULL", "ON", "OR",
+    "ORDER", "OUTER", "PRIMARY", "REFERENCES", "RIGHT", "SELECT", "SET", "TABLE", "THEN", "TO",
+    "TRUE", "UNION", "UNIQUE", "UPDATE", "USER", "USING", "VALUES", "WHEN", "WHERE", "WITH",
+];
+
+// MySQL 额外的保留关键字
+pub(crate) const MYSQL: &[&str] = &[
+    "ACCESSIBLE", "ANALYZE", "BEFORE", "BIGINT", "BINARY", "BLOB", "BOTH", "CALL", "CASCADE",
+    "CHANGE", "CHAR", "CHARACTER", "CONDITION", "CONTINUE", "CONVERT", "CURSOR", "DATABASE",
+    "DATABASES", "DAY_HOUR", "DEC", "DECIMAL", "DECLARE", "DELAYED", "DESCRIBE", "DIV", "DOUBLE",
+    "DUAL", "EACH", "ELSEIF", "ENCLOSED", "ESCAPED", "EXIT", "EXPLAIN", "FETCH", "FLOAT",
+    "FORCE", "FULLTEXT", "GENERATED", "GROUPS", "HIGH_PRIORITY", "IF", "IGNORE", "INDEX",
+    "INFILE", "INT", "INTEGER", "INTERVAL", "ITERATE", "KEY", "KEYS", "KILL", "LEADING", "LEAVE",
+    "LIMIT", "LINES", "LOAD", "LOCK", "LONG", "LOOP", "MATCH", "MOD", "NATURAL", "OPTIMIZE",
+    "OPTION", "OUT", "OVER", "PARTITION", "PRECISION", "PROCEDURE", "RANGE", "RANK", "READ",
+    "REAL", "REGEXP", "RELEASE", "RENAME", "REPEAT", "REPLACE", "REQUIRE", "RESTRICT", "RETURN",
+    "REVOKE", "RLIKE", "ROW", "ROWS", "SCHEMA", "SCHEMAS", "SEPARATOR", "SHOW", "SIGNAL",
+    "SMALLINT", "SPATIAL", "SQL", "STARTING", "STORED", "SYSTEM", "TERMINATED", "TRAILING",
+    "TRIGGER", "UNDO", "UNLOCK", "UNSIGNED", "USAGE", "USE", "VARCHAR", "VIRTUAL", "WHILE",
+    "WINDOW", "WRITE", "XOR", "ZEROFILL",
+];
+
+// PostgreSQL 额外的保留关键字
+pub(crate) const POSTGRESQL: &[&str] = &[
+    "ANALYSE", "ANALYZE", "ARRAY", "ASYMMETRIC", "AUTHORIZATION", "BINARY", "BOTH", "CAST",
+    "COLLATE", "COLLATION", "CONCURRENTLY", "CURRENT_CATALOG", "CURRENT_ROLE", "CURRENT_SCHEMA",
+    "DEFERRABLE", "DO", "EXCEPT", "FETCH", "FREEZE", "ILIKE", "INITIALLY", "ISNULL", "LATERAL",
+    "LEADING", "LIMIT", "LOCALTIME", "LOCALTIMESTAMP", "NATURAL", "NOTNULL", "OFFSET", "ONLY",
+    "OVERLAPS", "PLACING", "RETURNING", "SESSION_USER", "SIMILAR", "SOME", "SYMMETRIC",
+    "TABLESAMPLE", "TRAILING", "VARIADIC", "VERBOSE", "WINDOW",
+];
+
+// SQLite 额外的保留关键字
+pub(crate) const SQLITE: &[&str] = &[
+    "ABORT", "ACTION", "AFTER", "ANALYZE", "ATTACH", "AUTOINCREMENT", "BEFORE", "BEGIN",
+    "CASCADE", "CAST", "COLLATE", "COMMIT", "CONFLICT", "DATABASE", "DEFERRABLE", "DEFERRED",
+    "DETACH", "EACH", "ESCAPE", "EXCEPT", "EXCLUSIVE", "EXPLAIN", "FAIL", "GLOB", "IF", "IGNORE",
+    "IMMEDIATE", "INDEX", "INDEXED", "INITIALLY", "INSTEAD", "ISNULL", "KEY", "LIMIT", "MATCH",
+    "NATURAL", "NO", "NOTNULL", "OF", "OFFSET", "PLAN", "PRAGMA", "QUERY", "RAISE", "RECURSIVE",
+    "REGEXP", "REINDEX", "RELEASE", "RENAME", "REPLACE", "RESTRICT", "ROLLBACK", "ROW",
+    "SAVEPOINT", "TEMP", "TEMPORARY", "TRANSACTION", "TRIGGER", "VACUUM", "VIEW", "VIRTUAL",
+];
+
+// SQL Server 额外的保留关键字
+pub(crate) const SQLSERVER: &[&str] = &[
+    "BACKUP", "BEGIN", "BREAK", "BROWSE", "BULK", "CASCADE", "CHECKPOINT", "CLOSE", "CLUSTERED",
+    "COALESCE", "COLLATE", "COMMIT", "COMPUTE", "CONTAINS", "CONTAINSTABLE", "CONTINUE",
+    "CONVERT", "CURSOR", "DATABASE", "DBCC", "DEALLOCATE", "DECLARE", "DENY", "DISK",
+    "DISTRIBUTED", "DOUBLE", "DUMP", "ERRLVL", "ESCAPE", "EXCEPT", "EXEC", "EXECUTE", "EXIT",
+    "FETCH", "FILE", "FILLFACTOR", "FREETEXT", "FUNCTION", "GOTO", "HOLDLOCK", "IDENTITY",
+    "IF", "INDEX", "KEY", "KILL", "LINENO", "LOAD", "MERGE", "NOCHECK", "NONCLUSTERED", "OF",
+    "OFF", "OFFSETS", "OPEN", "OPTION", "OVER", "PERCENT", "PIVOT", "PLAN", "PRINT", "PROC",
+    "PROCEDURE", "PUBLIC", "RAISERROR", "READ", "RECONFIGURE", "REPLICATION", "RESTORE",
+    "RESTRICT", "RETURN", "REVERT", "REVOKE", "ROLLBACK", "ROWCOUNT", "RULE", "SAVE", "SCHEMA",
+    "SESSION_USER", "SHUTDOWN", "SOME", "STATISTICS", "SYSTEM_USER", "TEXTSIZE", "TOP", "TRAN",
+    "TRANSACTION", "TRIGGER", "TRUNCATE", "UNPIVOT", "USE", "VARYING", "VIEW", "WAITFOR",
+    "WHILE", "WRITETEXT",
+];
+
+// Oracle 额外的保留关键字
+pub(crate) const ORACLE: &[&str] = &[
+    "ACCESS", "AUDIT", "CHAR", "CLUSTER", "COMMENT", "COMPRESS", "CONNECT", "DATE", "DECIMAL",
+    "EXCLUSIVE", "FILE", "FLOAT", "IDENTIFIED", "IMMEDIATE", "INCREMENT", "INDEX", "INITIAL",
+    "INTEGER", "LEVEL", "LOCK", "LONG", "MAXEXTENTS", "MINUS", "MLSLABEL", "MODE", "MODIFY",
+    "NOAUDIT", "NOCOMPRESS", "NOWAIT", "NUMBER", "OF", "OFFLINE", "ONLINE", "OPTION", "PCTFREE",
+    "PRIOR", "PUBLIC", "RAW", "RENAME", "RESOURCE", "REVOKE", "ROW", "ROWID", "ROWNUM", "ROWS",
+    "SESSION", "SHARE", "SIZE", "SMALLINT", "START", "SUCCESSFUL", "SYNONYM", "SYSDATE",
+    "TRIGGER", "UID", "VALIDATE", "VARCHAR", "VARCHAR2", "VIEW", "WHENEVER",
+];
+
+// CQL 额外的保留关键字
+pub(crate) const CQL: &[&str] = &[
+    "ALLOW", "APPLY", "AUTHORIZE", "BATCH", "BEGIN", "COLUMNFAMILY", "DESCRIBE", "ENTRIES",
+    "EXECUTE", "IF", "INDEX", "INFINITY", "KEYSPACE", "LIMIT", "MODIFY", "NAN", "NORECURSIVE",
+    "OF", "PERMISSIONS", "RENAME", "REPLACE", "REVOKE", "SCHEMA", "TOKEN", "TRUNCATE", "USE",
+];
+
+// ClickHouse 额外的保留关键字
+pub(crate) const CLICKHOUSE: &[&str] = &[
+    "ARRAY", "ATTACH", "DATABASE", "DETACH", "DICTIONARY", "ENGINE", "FINAL", "FORMAT",
+    "GLOBAL", "IF", "ILIKE", "INDEX", "INTERVAL", "LIMIT", "OFFSET", "OPTIMIZE", "PARTITION",
+    "PREWHERE", "SAMPLE", "SETTINGS", "TOTALS", "TTL", "VIEW",
+];
+
+// Presto 额外的保留关键字
+pub(crate) const PRESTO: &[&str] = &[
+    "CAST", "CUBE", "DEALLOCATE", "DESCRIBE", "EXCEPT", "EXECUTE", "EXTRACT", "GROUPING",
+    "LOCALTIME", "LOCALTIMESTAMP", "NATURAL", "NORMALIZE", "PREPARE", "RECURSIVE", "ROLLUP",
+    "UESCAPE", "UNNEST",
+];
+
+// Informix 额外的保留关键字
+pub(crate) const INFORMIX: &[&str] = &[
+    "CURRENT", "DATABASE", "DATE", "EXTEND", "FIRST", "INDEX", "INTERVAL", "MATCHES", "SKIP",
+    "TODAY", "VIEW",
+];
+
+// DuckDB 额外的保留关键字
+pub(crate) const DUCKDB: &[&str] = &[
+    "ANALYSE", "ANALYZE", "ARRAY", "ASYMMETRIC", "BOTH", "CAST", "COLLATE", "DEFERRABLE", "DO",
+    "EXCEPT", "FETCH", "GLOB", "ILIKE", "INITIALLY", "LATERAL", "LEADING", "LIMIT", "NATURAL",
+    "OFFSET", "ONLY", "PIVOT", "PIVOT_LONGER", "PIVOT_WIDER", "PLACING", "QUALIFY", "RETURNING",
+    "SEMI", "SIMILAR", "SOME", "SUMMARIZE", "SYMMETRIC", "TRAILING", "UNPIVOT", "VARIADIC",
+    "WINDOW",
+];
+
+// Snowflake 额外的保留关键字
+pub(crate) const SNOWFLAKE: &[&str] = &[
+    "ACCOUNT", "CAST", "CONNECT", "CONNECTION", "DATABASE", "EXCEPT", "FOLLOWING", "GSCLUSTER",
+    "ILIKE", "INCREMENT", "ISSUE", "LATERAL", "LOCALTIME", "LOCALTIMESTAMP",
+    "MINUS", "NATURAL", "OF", "ORGANIZATION", "QUALIFY", "REGEXP", "REVOKE", "RLIKE", "ROW",
+    "ROWS", "SAMPLE", "SCHEMA", "SOME", "START", "TABLESAMPLE", "TRIGGER", "TRY_CAST", "VIEW",
+];
+
+// BigQuery 额外的保留关键字
+pub(crate) const BIGQUERY: &[&str] = &[
+    "ARRAY", "ASSERT_ROWS_MODIFIED", "AT", "COLLATE", "CONTAINS", "CUBE", "DEFINE", "ENUM",
+    "ESCAPE", "EXCEPT", "EXCLUDE", "EXTRACT", "FETCH", "FOLLOWING", "GROUPING", "GROUPS", "HASH",
+    "IF", "IGNORE", "INTERVAL", "LATERAL", "LIMIT", "LOOKUP", "MERGE", "NATURAL", "NEW", "NO",
+    "NULLS", "OF", "OVER", "PARTITION", "PRECEDING", "PROTO", "QUALIFY", "RANGE", "RECURSIVE",
+    "RESPECT", "ROLLUP", "ROWS", "SOME", "STRUCT", "TABLESAMPLE", "TREAT", "UNBOUNDED", "UNNEST",
+    "WINDOW", "WITHIN",
+];
+
+// Doris 和 StarRocks 额外的保留关键字
+pub(crate) const DORIS: &[&str] = &[
+    "BIGINT", "BITMAP", "BOOLEAN", "BUCKETS", "CHAR", "DATABASE", "DATABASES", "DATETIME",
+    "DECIMAL", "DESCRIBE", "DISTRIBUTED", "DOUBLE", "DUPLICATE", "EXPLAIN", "FLOAT", "HLL",
+    "IF", "INDEX", "INT", "INTERVAL", "KEY", "KEYS", "LARGEINT", "LIMIT", "LOAD", "OFFSET",
+    "OVER", "PARTITION", "PROPERTIES", "RANGE", "REPLACE", "ROLLUP", "SCHEMA", "SHOW",
+    "SMALLINT", "TINYINT", "VARCHAR",
+];
@@ -0,0 +1,232 @@
+use crate::dialect::Capabilities;
+
+// 词法单元类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TokenKind {
+    // 普通 SQL 代码，占位符只会出现在这里
+    Code,
+    // 字符串字面量，包括 '...'、E'...' 和 $tag$...$tag$
+    String,
+    // 加引号的标识符，例如 "name"、`name` 和 [name]
+    Identifier,
+    // 注释，包括 -- ...、# ... 和 /* ... */
+    Comment,
+}
+
+// 词法单元
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Token<'a> {
+    pub(crate) kind: TokenKind,
+    pub(crate) text: &'a str,
+}
+
+// SQL 词法分析器
+// 只区分代码、字符串、标识符和注释，足以判断占位符是否处于真正的代码位置
+pub(crate) struct Lexer<'a> {
+    sql: &'a str,
+    pos: usize,
+    caps: Capabilities,
+}
+
+impl<'a> Lexer<'a> {
+    // 创建一个新的 Lexer 实例
+    pub(crate) fn new(sql: &'a str, caps: Capabilities) -> Self {
+        Self { sql, pos: 0, caps }
+    }
+
+    // 判断 at 处是否开始一个非代码的词法单元，若是则返回其类型和结束位置
+    fn scan_special(&self, at: usize) -> Option<(TokenKind, usize)> {
+        let bytes = self.sql.as_bytes();
+        let caps = self.caps;
+
+        match bytes[at] {
+            b'\'' => Some((TokenKind::String, self.scan_quoted(at, b'\'', caps.backslash_escape))),
+            b'"' if caps.double_quote_string => {
+                Some((TokenKind::String, self.scan_quoted(at, b'"', caps.backslash_escape)))
+            }
+            b'"' => Some((TokenKind::Identifier, self.scan_quoted(at, b'"', false))),
+            b'`' if caps.backtick_ident => Some((TokenKind::Identifier, self.scan_quoted(at, b'`', false))),
+            b'[' if caps.bracket_ident => Some((TokenKind::Identifier, self.scan_quoted(at, b']', false))),
+            b'-' if bytes.get(at + 1) == Some(&b'-') => Some((TokenKind::Comment, self.scan_line(at))),
+            b'#' if caps.hash_comment => Some((TokenKind::Comment, self.scan_line(at))),
+            b'/' if bytes.get(at + 1) == Some(&b'*') => Some((TokenKind::Comment, self.scan_block_comment(at))),
+            b'E' | b'e'
+                if caps.escape_string
+                    && bytes.get(at + 1) == Some(&b'\'')
+                    && !self.follows_word(at) =>
+            {
+                Some((TokenKind::String, self.scan_quoted(at + 1, b'\'', true)))
+            }
+            b'$' if caps.dollar_quote && !self.follows_word(at) => {
+                self.scan_dollar_quoted(at).map(|end| (TokenKind::String, end))
+            }
+            _ => None,
+        }
+    }
+
+    // 判断 at 之前的字符是否属于一个单词
+    fn follows_word(&self, at: usize) -> bool {
+        at > 0 && {
+            let prev = self.sql.as_bytes()[at - 1];
+            prev.is_ascii_alphanumeric() || prev == b'_' || prev == b'$' || prev >= 0x80
+        }
+    }
+
+    // 扫描引号包围的内容，at 指向开引号
+    // 连续两个闭引号表示转义，返回闭引号之后的位置
+    fn scan_quoted(&self, at: usize, close: u8, backslash_escape: bool) -> usize {
+        let bytes = self.sql.as_bytes();
+        let mut i = at + 1;
+
+        while i < bytes.len() {
+            if backslash_escape && bytes[i] == b'\\' {
+                i += 2;
+                continue;
+            }
+
+            if bytes[i] == close {
+                if close != b']' && bytes.get(i + 1) == Some(&close) {
+                    i += 2;
+                    continue;
+                }
+
+                return i + 1;
+            }
+
+            i += 1;
+        }
+
+        bytes.len()
+    }
+
+    // 扫描单行注释，返回行尾位置（不含换行符）
+    fn scan_line(&self, at: usize) -> usize {
+        self.sql[at..].find('\n').map_or(self.sql.len(), |n| at + n)
+    }
+
+    // 扫描块注释，部分方言中块注释可以嵌套
+    fn scan_block_comment(&self, at: usize) -> usize {
+        let bytes = self.sql.as_bytes();
+        let mut depth = 0;
+        let mut i = at;
+
+        while i + 1 < bytes.len() {
+            if bytes[i] == b'/' && bytes[i + 1] == b'*' {
+                depth += 1;
+                i += 2;
+            } else if bytes[i] == b'*' && bytes[i + 1] == b'/' {
+                depth -= 1;
+                i += 2;
+
+                if depth == 0 || !self.caps.nested_comments {
+                    return i;
+                }
+            } else {
+                i += 1;
+            }
+        }
+
+        bytes.len()
+    }
+
+    // 扫描 $tag$...$tag$ 字符串，$1 这样的占位符不是字符串
+    fn scan_dollar_quoted(&self, at: usize) -> Option<usize> {
+        let rest = &self.sql[at + 1..];
+        let tag_len = rest.find('$')?;
+        let tag = &rest[..tag_len];
+
+        if tag.starts_with(|c: char| c.is_ascii_digit())
+            || !tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return None;
+        }
+
+        let delimiter = &self.sql[at..at + tag_len + 2];
+        let body = at + delimiter.len();
+
+        Some(
+            self.sql[body..]
+                .find(delimiter)
+                .map_or(self.sql.len(), |n| body + n + delimiter.len()),
+        )
+    }
+}
+
+impl<'a> Iterator for Lexer<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.pos;
+
+        if start >= self.sql.len() {
+            return None;
+        }
+
+        if let Some((kind, end)) = self.scan_special(start) {
+            self.pos = end.min(self.sql.len());
+            return Some(Token {
+                kind,
+                text: &self.sql[start..self.pos],
+            });
+        }
+
+        // 非代码单元都以 ASCII 字符开始，逐字节前进不会切断 UTF-8 字符
+        let mut end = start + 1;
+
+        while end < self.sql.len() && self.scan_special(end).is_none() {
+            end += 1;
+        }
+
+        self.pos = end;
+        Some(Token {
+            kind: TokenKind::Code,
+            text: &self.sql[start..end],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::flavor::Flavor;
+
+    fn kinds(sql: &str, flavor: Flavor) -> Vec<(TokenKind, &str)> {
+        Lexer::new(sql, flavor.capabilities()).map(|t| (t.kind, t.text)).collect()
+    }
+
+    #[test]
+    fn test_lexer_mysql() {
+        assert_eq!(
+            kinds("SELECT `a?`, 'it''s ?', \"\\\"?\" -- ?\n# ?\nFROM t", Flavor::MySQL),
+            vec![
+                (TokenKind::Code, "SELECT "),
+                (TokenKind::Identifier, "`a?`"),
+                (TokenKind::Code, ", "),
+                (TokenKind::String, "'it''s ?'"),
+                (TokenKind::Code, ", "),
+                (TokenKind::String, "\"\\\"?\""),
+                (TokenKind::Code, " "),
+                (TokenKind::Comment, "-- ?"),
+                (TokenKind::Code, "\n"),
+                (TokenKind::Comment, "# ?"),
+                (TokenKind::Code, "\nFROM t"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lexer_postgresql() {
+        assert_eq!(
+            kinds("SELECT $1::text, $fn$ $2 $fn$, E'\\' $3', /* /* $4 */ */ x$5", Flavor::PostgreSQL),
+            vec![
+                (TokenKind::Code, "SELECT $1::text, "),
+                (TokenKind::String, "$fn$ $2 $fn$"),
+                (TokenKind::Code, ", "),
+                (TokenKind::String, "E'\\' $3'"),
+                (TokenKind::Code, ", "),
+                (TokenKind::Comment, "/* /* $4 */ */"),
+                (TokenKind::Code, " x$5"),
+            ]
+        );
+    }
+}
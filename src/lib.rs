@@ -0,0 +1,36 @@
+// 各模块以库的形式提供，main 只是占位
+pub mod cond;
+pub mod column;
+pub mod expr;
+pub mod window;
+pub mod case;
+pub mod func;
+pub mod json;
+pub mod search;
+pub mod array;
+pub mod in_list;
+pub mod dialect;
+pub mod args;
+pub mod builder;
+pub mod stringbuilder;
+pub mod flavor;
+pub mod format;
+pub mod create_table;
+pub mod injection;
+pub mod interpolate;
+pub mod lexer;
+#[cfg(feature = "sqlx")]
+pub mod sqlx_support;
+#[cfg(feature = "rusqlite")]
+pub mod rusqlite_support;
+#[cfg(any(feature = "postgres", feature = "tokio-postgres"))]
+pub mod postgres_support;
+#[cfg(any(feature = "mysql", feature = "mysql_async"))]
+pub mod mysql_support;
+#[cfg(feature = "sea-query")]
+pub mod sea_query_support;
+pub mod error;
+pub mod value;
+pub mod keywords;
+#[cfg(test)]
+mod testing;
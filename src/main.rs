@@ -1,12 +1,3 @@
-mod cond;
-mod args;
-mod stringbuilder;
-mod flavor;
-mod create_table;
-mod injection;
-mod interpolate;
-mod error;
-
 fn main() {
     println!("Hello, world!");
 }
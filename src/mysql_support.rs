@@ -0,0 +1,384 @@
+use std::collections::{HashMap, HashSet};
+
+#[cfg(feature = "mysql")]
+use ::mysql::{Params, Value as MysqlValue};
+#[cfg(not(feature = "mysql"))]
+use ::mysql_async::{Params, Value as MysqlValue};
+
+use crate::args::{Args, Marker};
+use crate::builder::Builder;
+use crate::error::Error;
+use crate::flavor::Flavor;
+use crate::value::Value;
+
+// 将参数转换为 mysql 的 Value
+// MySQL 没有布尔类型，布尔值按 1 和 0 绑定；带时区的时间转换为 UTC，UUID、JSON 和 Decimal 按文本绑定
+pub fn to_mysql_value(arg: &Value) -> MysqlValue {
+    match arg {
+        Value::Null => MysqlValue::NULL,
+        Value::Bool(v) => MysqlValue::Int(i64::from(*v)),
+        Value::TinyInt(v) => MysqlValue::Int(i64::from(*v)),
+        Value::SmallInt(v) => MysqlValue::Int(i64::from(*v)),
+        Value::Int(v) => MysqlValue::Int(i64::from(*v)),
+        Value::BigInt(v) => MysqlValue::Int(*v),
+        Value::TinyUnsigned(v) => MysqlValue::UInt(u64::from(*v)),
+        Value::SmallUnsigned(v) => MysqlValue::UInt(u64::from(*v)),
+        Value::Unsigned(v) => MysqlValue::UInt(u64::from(*v)),
+        Value::BigUnsigned(v) => MysqlValue::UInt(*v),
+        Value::Float(v) => MysqlValue::Float(*v),
+        Value::Double(v) => MysqlValue::Double(*v),
+        Value::String(v) => MysqlValue::Bytes(v.as_bytes().to_vec()),
+        Value::Bytes(v) => MysqlValue::Bytes(v.clone()),
+        #[cfg(feature = "chrono")]
+        Value::ChronoDate(v) => chrono_date_time(v.and_time(chrono::NaiveTime::MIN)),
+        #[cfg(feature = "chrono")]
+        Value::ChronoTime(v) => {
+            use chrono::Timelike;
+            MysqlValue::Time(false, 0, v.hour() as u8, v.minute() as u8, v.second() as u8, v.nanosecond() / 1000)
+        }
+        #[cfg(feature = "chrono")]
+        Value::ChronoDateTime(v) => chrono_date_time(*v),
+        #[cfg(feature = "chrono")]
+        Value::ChronoDateTimeUtc(v) => chrono_date_time(v.naive_utc()),
+        #[cfg(feature = "chrono")]
+        Value::ChronoDateTimeWithTimeZone(v) => chrono_date_time(v.naive_utc()),
+        #[cfg(feature = "time")]
+        Value::TimeDate(v) => time_date_time(time::PrimitiveDateTime::new(*v, time::Time::MIDNIGHT)),
+        #[cfg(feature = "time")]
+        Value::TimeTime(v) => MysqlValue::Time(false, 0, v.hour(), v.minute(), v.second(), v.microsecond()),
+        #[cfg(feature = "time")]
+        Value::TimeDateTime(v) => time_date_time(*v),
+        #[cfg(feature = "time")]
+        Value::TimeDateTimeWithTimeZone(v) => {
+            let v = v.to_offset(time::UtcOffset::UTC);
+            time_date_time(time::PrimitiveDateTime::new(v.date(), v.time()))
+        }
+        // MySQL 没有数组类型，编译时已经拒绝
+        Value::Array(_) => MysqlValue::NULL,
+        #[allow(unreachable_patterns)]
+        other => MysqlValue::Bytes(other.to_text().unwrap_or_default().into_bytes()),
+    }
+}
+
+// 将 chrono 的日期时间转换为 mysql 的 Date
+#[cfg(feature = "chrono")]
+fn chrono_date_time(v: chrono::NaiveDateTime) -> MysqlValue {
+    use chrono::{Datelike, Timelike};
+
+    MysqlValue::Date(
+        v.year() as u16,
+        v.month() as u8,
+        v.day() as u8,
+        v.hour() as u8,
+        v.minute() as u8,
+        v.second() as u8,
+        v.nanosecond() / 1000,
+    )
+}
+
+// 将 time 的日期时间转换为 mysql 的 Date
+#[cfg(feature = "time")]
+fn time_date_time(v: time::PrimitiveDateTime) -> MysqlValue {
+    MysqlValue::Date(
+        v.year() as u16,
+        u8::from(v.month()),
+        v.day(),
+        v.hour(),
+        v.minute(),
+        v.second(),
+        v.microsecond(),
+    )
+}
+
+// MySQL 无法绑定数组，数组参数返回 UnsupportedArgs
+fn check_args(args: &[Value]) -> Result<(), Error> {
+    match args.iter().position(|v| matches!(v, Value::Array(_))) {
+        Some(index) => Err(Error::UnsupportedArgs { index, kind: "Array" }),
+        None => Ok(()),
+    }
+}
+
+// 将编译后的参数转换为按位置绑定的 Params
+pub fn mysql_params(args: &[Value]) -> Params {
+    if args.is_empty() {
+        return Params::Empty;
+    }
+
+    Params::Positional(args.iter().map(to_mysql_value).collect())
+}
+
+impl Args {
+    // 使用 MySQL 编译 SQL，参数按 ? 的位置绑定
+    pub fn compile_mysql(&self, format: &str) -> Result<(String, Params), Error> {
+        let (sql, args) = self.compile_with_flavor(format, Flavor::MySQL, Vec::new())?;
+        check_args(&args)?;
+        Ok((sql, mysql_params(&args)))
+    }
+
+    // 编译为使用 :name 命名占位符的 SQL，参数按名字绑定
+    // ${name} 编译为 :name，同一个名字只绑定一次；$n 按出现顺序编译为 :p1、:p2 等
+    // 命名参数与自动生成的 :p1、:p2 等同名时返回 DuplicateArgName
+    pub fn compile_mysql_named(&self, format: &str) -> Result<(String, Params), Error> {
+        let mut values = HashMap::new();
+        let mut generated = HashSet::new();
+        let mut positional = 0;
+        let mut index = 0;
+
+        let sql = self.compile_markers(format, &Flavor::MySQL, |buf, marker| {
+            index += 1;
+
+            let (name, arg) = match marker {
+                Marker::Value(_, Value::Array(_)) => return Err(Error::UnsupportedArgs { index: index - 1, kind: "Array" }),
+                Marker::Value(name, arg) => (name, arg),
+                // 子查询编译后只有 ? 占位符，无法与命名参数混用
+                Marker::Builder(index, _) => return Err(Error::UnsupportedArgs { index, kind: "Subquery" }),
+            };
+
+            let name = match name {
+                Some(name) if generated.contains(name) => return Err(Error::DuplicateArgName { name: name.to_string() }),
+                Some(name) => name.to_string(),
+                None => {
+                    positional += 1;
+                    let name = format!("p{}", positional);
+
+                    if values.contains_key(name.as_bytes()) {
+                        return Err(Error::DuplicateArgName { name });
+                    }
+
+                    generated.insert(name.clone());
+                    name
+                }
+            };
+
+            buf.push(':');
+            buf.push_str(&name);
+
+            values.insert(name.into_bytes(), to_mysql_value(arg));
+            Ok(())
+        })?;
+
+        if values.is_empty() {
+            return Ok((sql, Params::Empty));
+        }
+
+        Ok((sql, Params::Named(values)))
+    }
+}
+
+// 将 mysql 的错误转换为 Error
+#[cfg(feature = "mysql")]
+impl From<::mysql::Error> for Error {
+    fn from(err: ::mysql::Error) -> Self {
+        Error::Database(Box::new(err))
+    }
+}
+
+// 将 mysql_async 的错误转换为 Error
+#[cfg(feature = "mysql_async")]
+impl From<::mysql_async::Error> for Error {
+    fn from(err: ::mysql_async::Error) -> Self {
+        Error::Database(Box::new(err))
+    }
+}
+
+// MysqlBuilder 为所有构造器提供编译为 mysql 查询并执行的方法
+// SQL 使用构造器自身的 Flavor 编译，只接受 MySQL、MySQL57 和 MariaDB
+pub trait MysqlBuilder: Builder {
+    // 使用构造器的 Flavor 编译 SQL，并转换所有参数，Flavor 不属于 MySQL 一族时返回 UnsupportedFlavor
+    fn build_mysql(&self) -> Result<(String, Params), Error> {
+        let flavor = self.flavor();
+        if !matches!(flavor, Flavor::MySQL | Flavor::MySQL57 | Flavor::MariaDB) {
+            return Err(Error::UnsupportedFlavor { flavor, target: "mysql" });
+        }
+
+        let (sql, args) = self.build_with_flavor(flavor, Vec::new())?;
+        check_args(&args)?;
+        Ok((sql, mysql_params(&args)))
+    }
+
+    // 执行查询，并将每一行转换为 T
+    #[cfg(feature = "mysql")]
+    fn query<T, C>(&self, conn: &mut C) -> Result<Vec<T>, Error>
+    where
+        T: ::mysql::prelude::FromRow,
+        C: ::mysql::prelude::Queryable,
+    {
+        let (sql, params) = self.build_mysql()?;
+        Ok(conn.exec(sql, params)?)
+    }
+
+    // 执行 INSERT 语句并返回最后插入的自增 ID，没有生成 ID 时返回 None
+    #[cfg(feature = "mysql")]
+    fn insert<C: ::mysql::prelude::Queryable>(&self, conn: &mut C) -> Result<Option<u64>, Error> {
+        let (sql, params) = self.build_mysql()?;
+        let result = conn.exec_iter(sql, params)?;
+        Ok(result.last_insert_id().filter(|&id| id != 0))
+    }
+
+    // 执行 UPDATE 或 DELETE 等语句并返回受影响的行数
+    #[cfg(feature = "mysql")]
+    fn execute<C: ::mysql::prelude::Queryable>(&self, conn: &mut C) -> Result<u64, Error> {
+        let (sql, params) = self.build_mysql()?;
+        let result = conn.exec_iter(sql, params)?;
+        Ok(result.affected_rows())
+    }
+}
+
+impl<B: Builder + ?Sized> MysqlBuilder for B {}
+
+// MysqlAsyncBuilder 为所有构造器提供在 mysql_async 连接上执行的方法
+// SQL 和参数在调用时立即编译，返回的 Future 不借用构造器，可以跨线程使用
+// 方法名带有 _async 后缀，同时启用 mysql 和 mysql_async 时不会与 MysqlBuilder 冲突
+#[cfg(feature = "mysql_async")]
+pub trait MysqlAsyncBuilder: Builder {
+    // 执行查询，并将每一行转换为 T
+    fn query_async<'c, T, C>(
+        &self,
+        conn: &'c mut C,
+    ) -> impl std::future::Future<Output = Result<Vec<T>, Error>> + Send + 'c
+    where
+        T: ::mysql_async::prelude::FromRow + Send + 'static,
+        C: ::mysql_async::prelude::Queryable,
+    {
+        let built = self.build_mysql();
+
+        async move {
+            let (sql, params) = built?;
+            Ok(conn.exec(sql, params).await?)
+        }
+    }
+
+    // 执行 INSERT 语句并返回最后插入的自增 ID，没有生成 ID 时返回 None
+    fn insert_async<'c, C>(
+        &self,
+        conn: &'c mut C,
+    ) -> impl std::future::Future<Output = Result<Option<u64>, Error>> + Send + 'c
+    where
+        C: ::mysql_async::prelude::Queryable,
+    {
+        let built = self.build_mysql();
+
+        async move {
+            let (sql, params) = built?;
+            let result = conn.exec_iter(sql, params).await?;
+            let id = result.last_insert_id().filter(|&id| id != 0);
+            result.drop_result().await?;
+            Ok(id)
+        }
+    }
+
+    // 执行 UPDATE 或 DELETE 等语句并返回受影响的行数
+    fn execute_async<'c, C>(&self, conn: &'c mut C) -> impl std::future::Future<Output = Result<u64, Error>> + Send + 'c
+    where
+        C: ::mysql_async::prelude::Queryable,
+    {
+        let built = self.build_mysql();
+
+        async move {
+            let (sql, params) = built?;
+            let result = conn.exec_iter(sql, params).await?;
+            let affected = result.affected_rows();
+            result.drop_result().await?;
+            Ok(affected)
+        }
+    }
+}
+
+#[cfg(feature = "mysql_async")]
+impl<B: Builder + ?Sized> MysqlAsyncBuilder for B {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_table::CreateTableBuilder;
+
+    #[test]
+    fn test_build_mysql() {
+        let mut ctb = CreateTableBuilder::new();
+        let name = ctb.var("it's");
+        let id = ctb.var(7u32);
+        let active = ctb.var(true);
+        ctb.create_table("t")
+            .define(vec!["id".to_string(), "INT".to_string(), "DEFAULT".to_string(), id])
+            .define(vec!["name".to_string(), "TEXT".to_string(), "DEFAULT".to_string(), name])
+            .define(vec!["active".to_string(), "BOOL".to_string(), "DEFAULT".to_string(), active]);
+
+        let (sql, params) = ctb.build_mysql().unwrap();
+        assert_eq!(
+            sql,
+            "CREATE TABLE t (id INT DEFAULT ?, name TEXT DEFAULT ?, active BOOL DEFAULT ?)"
+        );
+        assert_eq!(
+            params,
+            Params::Positional(vec![MysqlValue::UInt(7), MysqlValue::Bytes(b"it's".to_vec()), MysqlValue::Int(1)])
+        );
+
+        let ctb = CreateTableBuilder::new();
+        assert_eq!(ctb.build_mysql().unwrap().1, Params::Empty);
+
+        let mut ctb = CreateTableBuilder::new();
+        let big = ctb.var(u64::MAX);
+        let none = ctb.var(None::<i32>);
+        ctb.create_table("t").define(vec![big, none]);
+        assert_eq!(
+            ctb.build_mysql().unwrap().1,
+            Params::Positional(vec![MysqlValue::UInt(u64::MAX), MysqlValue::NULL])
+        );
+
+        // 使用构造器自身的 Flavor，不属于 MySQL 一族时返回错误
+        let mut ctb = CreateTableBuilder::new();
+        ctb.set_flavor(Flavor::MariaDB);
+        ctb.create_table("t").if_not_exists().define(vec!["id".to_string(), "INT".to_string()]);
+        assert_eq!(ctb.build_mysql().unwrap().0, "CREATE TABLE IF NOT EXISTS t (id INT)");
+
+        ctb.set_flavor(Flavor::PostgreSQL);
+        assert!(matches!(
+            ctb.build_mysql(),
+            Err(Error::UnsupportedFlavor { flavor: Flavor::PostgreSQL, target: "mysql" })
+        ));
+    }
+
+    #[test]
+    fn test_compile_mysql_named() {
+        let mut args = Args::new();
+        let id = args.add_named("id", 1);
+        let name = args.add("foo");
+        let format = format!("UPDATE t SET name = {} WHERE id = {} OR parent = {} AND x = ${{y}}", name, id, id);
+
+        let (sql, params) = args.compile_mysql(&format).unwrap();
+        assert_eq!(sql, "UPDATE t SET name = ? WHERE id = ? OR parent = ? AND x = ${y}");
+        assert_eq!(
+            params,
+            Params::Positional(vec![MysqlValue::Bytes(b"foo".to_vec()), MysqlValue::Int(1), MysqlValue::Int(1)])
+        );
+
+        // 再次添加同名参数会覆盖之前的值
+        args.add_named("id", 2);
+        let (sql, params) = args.compile_mysql_named(&format).unwrap();
+        assert_eq!(sql, "UPDATE t SET name = :p1 WHERE id = :id OR parent = :id AND x = ${y}");
+        assert_eq!(
+            params,
+            Params::Named(HashMap::from([
+                (b"p1".to_vec(), MysqlValue::Bytes(b"foo".to_vec())),
+                (b"id".to_vec(), MysqlValue::Int(2)),
+            ]))
+        );
+
+        assert!(matches!(
+            args.compile_mysql("SELECT $0, $2"),
+            Err(Error::MissingArgs { expected: 3, got: 2, at: 11 })
+        ));
+
+        // 命名参数不能与自动命名的位置参数同名，无论谁先出现
+        let mut args = Args::new();
+        let named = args.add_named("p1", 1);
+        let positional = args.add(2);
+
+        for format in [format!("{} {}", named, positional), format!("{} {}", positional, named)] {
+            assert!(matches!(
+                args.compile_mysql_named(&format),
+                Err(Error::DuplicateArgName { name }) if name == "p1"
+            ));
+        }
+    }
+}
@@ -0,0 +1,274 @@
+use std::error::Error as StdError;
+
+use ::bytes::BytesMut;
+use ::tokio_postgres::types::{IsNull, ToSql, Type};
+
+use crate::args::Args;
+use crate::builder::Builder;
+use crate::error::Error;
+use crate::flavor::Flavor;
+use crate::value::Value;
+
+// Value 可以直接绑定到 postgres 查询
+// PostgreSQL 的类型检查很严格，整数按参数的实际类型 INT2、INT4 或 INT8 转换，超出范围时返回错误
+// REAL 可以绑定到 DOUBLE PRECISION 的参数，NULL 可以绑定到任意类型的参数，其他值交给实际类型检查
+// 数组绑定为 PostgreSQL 的数组类型，例如 = ANY($1) 中的 $1
+impl ToSql for Value {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn StdError + Sync + Send>> {
+        self.to_sql_checked(ty, out)
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+
+    fn to_sql_checked(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn StdError + Sync + Send>> {
+        match self {
+            Value::Null => Ok(IsNull::Yes),
+            Value::Bool(v) => v.to_sql_checked(ty, out),
+            Value::TinyInt(v) => integer_to_sql(i64::from(*v), ty, out),
+            Value::SmallInt(v) => integer_to_sql(i64::from(*v), ty, out),
+            Value::Int(v) => integer_to_sql(i64::from(*v), ty, out),
+            Value::BigInt(v) => integer_to_sql(*v, ty, out),
+            Value::TinyUnsigned(v) => integer_to_sql(i64::from(*v), ty, out),
+            Value::SmallUnsigned(v) => integer_to_sql(i64::from(*v), ty, out),
+            Value::Unsigned(v) => integer_to_sql(i64::from(*v), ty, out),
+            Value::BigUnsigned(v) => integer_to_sql(i64::try_from(*v)?, ty, out),
+            Value::Float(v) if *ty == Type::FLOAT8 => f64::from(*v).to_sql_checked(ty, out),
+            Value::Float(v) => v.to_sql_checked(ty, out),
+            Value::Double(v) => v.to_sql_checked(ty, out),
+            Value::String(v) => v.to_sql_checked(ty, out),
+            Value::Bytes(v) => v.to_sql_checked(ty, out),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDate(v) => v.to_sql_checked(ty, out),
+            #[cfg(feature = "chrono")]
+            Value::ChronoTime(v) => v.to_sql_checked(ty, out),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDateTime(v) => v.to_sql_checked(ty, out),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDateTimeUtc(v) => v.to_sql_checked(ty, out),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDateTimeWithTimeZone(v) => v.to_sql_checked(ty, out),
+            #[cfg(feature = "time")]
+            Value::TimeDate(v) => v.to_sql_checked(ty, out),
+            #[cfg(feature = "time")]
+            Value::TimeTime(v) => v.to_sql_checked(ty, out),
+            #[cfg(feature = "time")]
+            Value::TimeDateTime(v) => v.to_sql_checked(ty, out),
+            #[cfg(feature = "time")]
+            Value::TimeDateTimeWithTimeZone(v) => v.to_sql_checked(ty, out),
+            #[cfg(feature = "uuid")]
+            Value::Uuid(v) => v.to_sql_checked(ty, out),
+            #[cfg(feature = "serde_json")]
+            Value::Json(v) => v.to_sql_checked(ty, out),
+            #[cfg(feature = "rust_decimal")]
+            Value::Decimal(v) => v.to_sql_checked(ty, out),
+            // 元素按数组的元素类型逐个转换
+            Value::Array(v) => v.to_sql_checked(ty, out),
+        }
+    }
+}
+
+// 将整数转换为参数实际的整数类型，其他类型交给 i64 的类型检查
+fn integer_to_sql(v: i64, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn StdError + Sync + Send>> {
+    match *ty {
+        Type::INT2 => i16::try_from(v)?.to_sql_checked(ty, out),
+        Type::INT4 => i32::try_from(v)?.to_sql_checked(ty, out),
+        _ => v.to_sql_checked(ty, out),
+    }
+}
+
+// PostgresArgs 保存按 $n 顺序转换后的参数
+#[derive(Debug, Clone, PartialEq)]
+pub struct PostgresArgs {
+    values: Vec<Value>,
+}
+
+impl PostgresArgs {
+    // 检查编译后的参数，超出 i64 范围的无符号整数返回 UnsupportedArgs
+    pub fn new(values: Vec<Value>) -> Result<Self, Error> {
+        if let Some(index) = values.iter().position(|v| matches!(v, Value::BigUnsigned(v) if i64::try_from(*v).is_err())) {
+            return Err(Error::UnsupportedArgs { index, kind: "BigUnsigned" });
+        }
+
+        Ok(Self { values })
+    }
+
+    // 返回所有参数
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+
+    // 返回可以直接传给 query 和 execute 的参数，第 n 个元素对应 $n
+    pub fn as_params(&self) -> Vec<&(dyn ToSql + Sync)> {
+        self.values.iter().map(|v| v as &(dyn ToSql + Sync)).collect()
+    }
+}
+
+impl Args {
+    // 使用 PostgreSQL 编译 SQL，并转换所有参数
+    pub fn compile_postgres(&self, format: &str) -> Result<(String, PostgresArgs), Error> {
+        let (sql, args) = self.compile_with_flavor(format, Flavor::PostgreSQL, Vec::new())?;
+        Ok((sql, PostgresArgs::new(args)?))
+    }
+}
+
+// 将 postgres 的错误转换为 Error
+impl From<::tokio_postgres::Error> for Error {
+    fn from(err: ::tokio_postgres::Error) -> Self {
+        Error::Database(Box::new(err))
+    }
+}
+
+// PostgresBuilder 为所有构造器提供编译为 postgres 查询的方法
+// SQL 总是使用 PostgreSQL 编译，与构造器自身的 Flavor 无关
+pub trait PostgresBuilder: Builder {
+    // 使用 PostgreSQL 编译 SQL，并转换所有参数
+    fn build_postgres(&self) -> Result<(String, PostgresArgs), Error> {
+        let (sql, args) = self.build_with_flavor(Flavor::PostgreSQL, Vec::new())?;
+        Ok((sql, PostgresArgs::new(args)?))
+    }
+
+    // 执行查询并返回所有行
+    #[cfg(feature = "postgres")]
+    fn query<C: ::postgres::GenericClient>(&self, client: &mut C) -> Result<Vec<::postgres::Row>, Error> {
+        let (sql, args) = self.build_postgres()?;
+        Ok(client.query(sql.as_str(), &args.as_params())?)
+    }
+
+    // 执行查询并返回唯一的一行
+    #[cfg(feature = "postgres")]
+    fn query_one<C: ::postgres::GenericClient>(&self, client: &mut C) -> Result<::postgres::Row, Error> {
+        let (sql, args) = self.build_postgres()?;
+        Ok(client.query_one(sql.as_str(), &args.as_params())?)
+    }
+
+    // 执行语句并返回受影响的行数
+    #[cfg(feature = "postgres")]
+    fn execute<C: ::postgres::GenericClient>(&self, client: &mut C) -> Result<u64, Error> {
+        let (sql, args) = self.build_postgres()?;
+        Ok(client.execute(sql.as_str(), &args.as_params())?)
+    }
+}
+
+impl<B: Builder + ?Sized> PostgresBuilder for B {}
+
+// TokioPostgresBuilder 为所有构造器提供在 tokio-postgres 客户端上执行的方法
+// SQL 和参数在调用时立即编译，返回的 Future 不借用构造器，可以跨线程使用
+#[cfg(feature = "tokio-postgres")]
+pub trait TokioPostgresBuilder: Builder {
+    // 执行查询并返回所有行
+    fn query<'c, C>(
+        &self,
+        client: &'c C,
+    ) -> impl std::future::Future<Output = Result<Vec<::tokio_postgres::Row>, Error>> + Send + 'c
+    where
+        C: ::tokio_postgres::GenericClient + Sync,
+    {
+        let built = self.build_postgres();
+
+        async move {
+            let (sql, args) = built?;
+            Ok(client.query(sql.as_str(), &args.as_params()).await?)
+        }
+    }
+
+    // 执行查询并返回唯一的一行
+    fn query_one<'c, C>(
+        &self,
+        client: &'c C,
+    ) -> impl std::future::Future<Output = Result<::tokio_postgres::Row, Error>> + Send + 'c
+    where
+        C: ::tokio_postgres::GenericClient + Sync,
+    {
+        let built = self.build_postgres();
+
+        async move {
+            let (sql, args) = built?;
+            Ok(client.query_one(sql.as_str(), &args.as_params()).await?)
+        }
+    }
+
+    // 执行语句并返回受影响的行数
+    fn execute<'c, C>(&self, client: &'c C) -> impl std::future::Future<Output = Result<u64, Error>> + Send + 'c
+    where
+        C: ::tokio_postgres::GenericClient + Sync,
+    {
+        let built = self.build_postgres();
+
+        async move {
+            let (sql, args) = built?;
+            Ok(client.execute(sql.as_str(), &args.as_params()).await?)
+        }
+    }
+}
+
+#[cfg(feature = "tokio-postgres")]
+impl<B: Builder + ?Sized> TokioPostgresBuilder for B {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::RawBuilder;
+
+    #[test]
+    fn test_build_postgres() {
+        let insert = RawBuilder::new(|args| {
+            let id = args.add(7i32);
+            format!(
+                "INSERT INTO t (name, id, ratio) SELECT {}, {}, {} WHERE NOT EXISTS (SELECT 1 FROM t WHERE id = {})",
+                args.add("it's"),
+                id,
+                args.add(0.5f32),
+                id,
+            )
+        });
+
+        let (sql, args) = insert.build_postgres().unwrap();
+        assert_eq!(
+            sql,
+            "INSERT INTO t (name, id, ratio) SELECT $1, $2, $3 WHERE NOT EXISTS (SELECT 1 FROM t WHERE id = $4)"
+        );
+        assert_eq!(
+            args.values(),
+            &[
+                Value::String("it's".to_string()),
+                Value::Int(7),
+                Value::Float(0.5),
+                Value::Int(7),
+            ]
+        );
+        assert_eq!(args.as_params().len(), 4);
+
+        let select = RawBuilder::new(|args| format!("SELECT * FROM t WHERE id = {}", args.add(u64::MAX)));
+        assert!(matches!(select.build_postgres(), Err(Error::UnsupportedArgs { index: 0, kind: "BigUnsigned" })));
+    }
+
+    #[test]
+    fn test_to_sql() {
+        let mut out = BytesMut::new();
+
+        assert!(matches!(Value::Int(1).to_sql_checked(&Type::INT4, &mut out), Ok(IsNull::No)));
+        assert!(matches!(Value::Int(1).to_sql_checked(&Type::INT8, &mut out), Ok(IsNull::No)));
+        assert!(matches!(Value::SmallInt(1).to_sql_checked(&Type::INT8, &mut out), Ok(IsNull::No)));
+        assert!(matches!(Value::BigInt(1).to_sql_checked(&Type::INT2, &mut out), Ok(IsNull::No)));
+        assert!(Value::Int(70000).to_sql_checked(&Type::INT2, &mut out).is_err());
+        assert!(Value::Int(1).to_sql_checked(&Type::TEXT, &mut out).is_err());
+        assert!(matches!(Value::TinyUnsigned(1).to_sql_checked(&Type::INT2, &mut out), Ok(IsNull::No)));
+        assert!(Value::BigUnsigned(u64::MAX).to_sql_checked(&Type::INT8, &mut out).is_err());
+        assert!(matches!(Value::Float(0.5).to_sql_checked(&Type::FLOAT8, &mut out), Ok(IsNull::No)));
+        assert!(matches!(Value::String("a".to_string()).to_sql_checked(&Type::VARCHAR, &mut out), Ok(IsNull::No)));
+        assert!(matches!(Value::Null.to_sql_checked(&Type::INT4, &mut out), Ok(IsNull::Yes)));
+        assert!(matches!(Value::Null.to_sql_checked(&Type::BYTEA, &mut out), Ok(IsNull::Yes)));
+
+        out.clear();
+        Value::Int(7).to_sql_checked(&Type::INT8, &mut out).unwrap();
+        assert_eq!(&out[..], &7i64.to_be_bytes());
+
+        let mut args = Args::new();
+        let format = format!("SELECT {}, {}", args.add(3u8), args.add(()));
+        let (sql, args) = args.compile_postgres(&format).unwrap();
+        assert_eq!(sql, "SELECT $1, $2");
+        assert_eq!(args.values(), &[Value::TinyUnsigned(3), Value::Null]);
+    }
+}
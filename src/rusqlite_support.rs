@@ -0,0 +1,203 @@
+use ::rusqlite::types::Value as SqliteValue;
+use ::rusqlite::{params_from_iter, Connection, ParamsFromIter, Row};
+
+use crate::args::Args;
+use crate::builder::Builder;
+use crate::error::Error;
+use crate::flavor::Flavor;
+use crate::value::Value;
+
+// 将参数转换为 rusqlite 的 Value，超出 i64 范围的无符号整数返回 UnsupportedArgs，下标由调用方补充
+// SQLite 没有布尔类型，布尔值按 1 和 0 存储；日期时间、UUID、JSON 和 Decimal 按文本存储
+pub fn to_sqlite_value(arg: &Value) -> Result<SqliteValue, Error> {
+    Ok(match arg {
+        Value::Null => SqliteValue::Null,
+        Value::Bool(v) => SqliteValue::Integer(i64::from(*v)),
+        Value::TinyInt(v) => SqliteValue::Integer(i64::from(*v)),
+        Value::SmallInt(v) => SqliteValue::Integer(i64::from(*v)),
+        Value::Int(v) => SqliteValue::Integer(i64::from(*v)),
+        Value::BigInt(v) => SqliteValue::Integer(*v),
+        Value::TinyUnsigned(v) => SqliteValue::Integer(i64::from(*v)),
+        Value::SmallUnsigned(v) => SqliteValue::Integer(i64::from(*v)),
+        Value::Unsigned(v) => SqliteValue::Integer(i64::from(*v)),
+        Value::BigUnsigned(v) => match i64::try_from(*v) {
+            Ok(v) => SqliteValue::Integer(v),
+            Err(_) => return Err(Error::UnsupportedArgs { index: 0, kind: arg.kind() }),
+        },
+        Value::Float(v) => SqliteValue::Real(f64::from(*v)),
+        Value::Double(v) => SqliteValue::Real(*v),
+        Value::String(v) => SqliteValue::Text(v.clone()),
+        Value::Bytes(v) => SqliteValue::Blob(v.clone()),
+        #[allow(unreachable_patterns)]
+        other => match other.to_text() {
+            Some(text) => SqliteValue::Text(text),
+            None => return Err(Error::UnsupportedArgs { index: 0, kind: other.kind() }),
+        },
+    })
+}
+
+// 将编译后的参数转换为 rusqlite 的 Params
+pub fn sqlite_params(args: &[Value]) -> Result<ParamsFromIter<Vec<SqliteValue>>, Error> {
+    let values = args
+        .iter()
+        .enumerate()
+        .map(|(i, arg)| to_sqlite_value(arg).map_err(|err| err.at_index(i)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(params_from_iter(values))
+}
+
+impl Args {
+    // 使用 SQLite 编译 SQL，并返回可以直接传给 rusqlite 的参数
+    pub fn compile_rusqlite(&self, format: &str) -> Result<(String, ParamsFromIter<Vec<SqliteValue>>), Error> {
+        let (sql, args) = self.compile_with_flavor(format, Flavor::SQLite, Vec::new())?;
+        Ok((sql, sqlite_params(&args)?))
+    }
+}
+
+// 将 rusqlite 的错误转换为 Error
+impl From<::rusqlite::Error> for Error {
+    fn from(err: ::rusqlite::Error) -> Self {
+        Error::Database(Box::new(err))
+    }
+}
+
+// RusqliteBuilder 为所有构造器提供在 rusqlite 连接上执行的方法
+// SQL 总是使用 SQLite 编译，与构造器自身的 Flavor 无关
+pub trait RusqliteBuilder: Builder {
+    // 使用 SQLite 编译 SQL，并转换所有参数
+    fn build_rusqlite(&self) -> Result<(String, ParamsFromIter<Vec<SqliteValue>>), Error> {
+        let (sql, args) = self.build_with_flavor(Flavor::SQLite, Vec::new())?;
+        Ok((sql, sqlite_params(&args)?))
+    }
+
+    // 执行语句并返回受影响的行数
+    fn execute(&self, conn: &Connection) -> Result<usize, Error> {
+        let (sql, params) = self.build_rusqlite()?;
+        Ok(conn.execute(&sql, params)?)
+    }
+
+    // 执行查询，并使用 f 将每一行映射为 T
+    fn query_map<T, F>(&self, conn: &Connection, f: F) -> Result<Vec<T>, Error>
+    where
+        F: FnMut(&Row<'_>) -> ::rusqlite::Result<T>,
+    {
+        let (sql, params) = self.build_rusqlite()?;
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt.query_map(params, f)?;
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    // 执行查询，并使用 f 映射第一行
+    fn query_row<T, F>(&self, conn: &Connection, f: F) -> Result<T, Error>
+    where
+        F: FnOnce(&Row<'_>) -> ::rusqlite::Result<T>,
+    {
+        let (sql, params) = self.build_rusqlite()?;
+        Ok(conn.query_row(&sql, params, f)?)
+    }
+}
+
+impl<B: Builder + ?Sized> RusqliteBuilder for B {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_table::CreateTableBuilder;
+    use crate::testing::RawBuilder;
+
+    fn create_users(conn: &Connection) {
+        let mut ctb = CreateTableBuilder::new();
+        ctb.create_table("users")
+            .if_not_exists()
+            .define(vec!["id".to_string(), "INTEGER".to_string(), "PRIMARY KEY".to_string()])
+            .define(vec!["name".to_string(), "TEXT".to_string(), "NOT NULL".to_string()])
+            .define(vec!["active".to_string(), "BOOLEAN".to_string()])
+            .define(vec!["score".to_string(), "REAL".to_string()]);
+
+        assert_eq!(ctb.execute(conn).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_create_table() {
+        let conn = Connection::open_in_memory().unwrap();
+        create_users(&conn);
+
+        // 已存在的表不会报错
+        create_users(&conn);
+
+        let mut ctb = CreateTableBuilder::new();
+        ctb.create_temp_table("order")
+            .auto_quote()
+            .define(vec!["id".to_string(), "INTEGER".to_string()])
+            .sql("/* order */");
+        ctb.execute(&conn).unwrap();
+
+        let mut ctb = CreateTableBuilder::new();
+        ctb.create_table("users").define(vec!["id".to_string()]);
+        assert!(matches!(ctb.execute(&conn), Err(Error::Database(_))));
+    }
+
+    #[test]
+    fn test_execute_and_query() {
+        let conn = Connection::open_in_memory().unwrap();
+        create_users(&conn);
+
+        for (id, name, active, score) in [(1, "alice", true, 9.5), (2, "bob", false, 7.0)] {
+            let insert = RawBuilder::new(|args| {
+                format!(
+                    "INSERT INTO users (id, name, active, score) VALUES ({}, {}, {}, {})",
+                    args.add(id),
+                    args.add(name),
+                    args.add(active),
+                    args.add(score),
+                )
+            });
+            assert_eq!(insert.execute(&conn).unwrap(), 1);
+        }
+
+        let insert = RawBuilder::new(|args| {
+            format!(
+                "INSERT INTO users (id, name, active, score) VALUES ({}, {}, {}, {})",
+                args.add(3u8),
+                args.add("carol".to_string()),
+                args.add(()),
+                args.add(()),
+            )
+        });
+        insert.execute(&conn).unwrap();
+
+        let select = RawBuilder::new(|args| {
+            format!(
+                "SELECT name, active FROM users WHERE id <> {} ORDER BY id",
+                args.add(2i64),
+            )
+        });
+        let rows = select
+            .query_map(&conn, |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<bool>>(1)?)))
+            .unwrap();
+        assert_eq!(rows, vec![("alice".to_string(), Some(true)), ("carol".to_string(), None)]);
+
+        // 同一个参数可以被引用多次
+        let select = RawBuilder::new(|args| {
+            let name = args.add("bob");
+            format!("SELECT count(*) FROM users WHERE name = {} OR name || '' = {}", name, name)
+        });
+        let count: i64 = select.query_row(&conn, |row| row.get(0)).unwrap();
+        assert_eq!(count, 1);
+
+        let mut args = Args::new();
+        let format = format!("SELECT score FROM users WHERE id = {}", args.add(1));
+        let (sql, params) = args.compile_rusqlite(&format).unwrap();
+        assert_eq!(sql, "SELECT score FROM users WHERE id = ?");
+        let score: f64 = conn.query_row(&sql, params, |row| row.get(0)).unwrap();
+        assert_eq!(score, 9.5);
+    }
+
+    #[test]
+    fn test_unsupported_args() {
+        let conn = Connection::open_in_memory().unwrap();
+
+        let select = RawBuilder::new(|args| format!("SELECT {}", args.add(u64::MAX)));
+        assert!(matches!(select.execute(&conn), Err(Error::UnsupportedArgs { index: 0, kind: "BigUnsigned" })));
+    }
+}
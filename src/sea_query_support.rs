@@ -0,0 +1,631 @@
+use ::sea_query::{
+    Alias, ArrayType, BinOper, CaseStatement, ColumnName, ColumnRef, DatabaseName, DynIden, Expr as SeaExpr, Func,
+    FunctionCall, IntoIden, Keyword, MysqlQueryBuilder, PostgresQueryBuilder, QueryStatementBuilder, SchemaName,
+    SqliteQueryBuilder, TableName, UnOper, Value as SeaValue,
+};
+
+use crate::builder::{Builder, BuildMode};
+use crate::dialect::{self, Dialect};
+use crate::error::Error;
+use crate::expr::{BinaryOp, Expr, UnaryOp};
+use crate::flavor::{default_flavor, Flavor};
+use crate::func::Function;
+use crate::interpolate::interpolate_with;
+use crate::value::Value;
+
+// 将参数转换为 sea_query 的 Value，NULL 转换为空的 String
+// 数组的元素类型取第一个非空元素的类型，没有时为 String
+pub fn to_sea_value(arg: &Value) -> SeaValue {
+    match arg {
+        Value::Null => SeaValue::String(None),
+        Value::Bool(v) => SeaValue::from(*v),
+        Value::TinyInt(v) => SeaValue::from(*v),
+        Value::SmallInt(v) => SeaValue::from(*v),
+        Value::Int(v) => SeaValue::from(*v),
+        Value::BigInt(v) => SeaValue::from(*v),
+        Value::TinyUnsigned(v) => SeaValue::from(*v),
+        Value::SmallUnsigned(v) => SeaValue::from(*v),
+        Value::Unsigned(v) => SeaValue::from(*v),
+        Value::BigUnsigned(v) => SeaValue::from(*v),
+        Value::Float(v) => SeaValue::from(*v),
+        Value::Double(v) => SeaValue::from(*v),
+        Value::String(v) => SeaValue::from(v.clone()),
+        Value::Bytes(v) => SeaValue::from(v.clone()),
+        #[cfg(feature = "chrono")]
+        Value::ChronoDate(v) => SeaValue::from(*v),
+        #[cfg(feature = "chrono")]
+        Value::ChronoTime(v) => SeaValue::from(*v),
+        #[cfg(feature = "chrono")]
+        Value::ChronoDateTime(v) => SeaValue::from(*v),
+        #[cfg(feature = "chrono")]
+        Value::ChronoDateTimeUtc(v) => SeaValue::from(*v),
+        #[cfg(feature = "chrono")]
+        Value::ChronoDateTimeWithTimeZone(v) => SeaValue::from(*v),
+        #[cfg(feature = "time")]
+        Value::TimeDate(v) => SeaValue::from(*v),
+        #[cfg(feature = "time")]
+        Value::TimeTime(v) => SeaValue::from(*v),
+        #[cfg(feature = "time")]
+        Value::TimeDateTime(v) => SeaValue::from(*v),
+        #[cfg(feature = "time")]
+        Value::TimeDateTimeWithTimeZone(v) => SeaValue::from(*v),
+        #[cfg(feature = "uuid")]
+        Value::Uuid(v) => SeaValue::from(*v),
+        #[cfg(feature = "serde_json")]
+        Value::Json(v) => SeaValue::from(v.clone()),
+        #[cfg(feature = "rust_decimal")]
+        Value::Decimal(v) => SeaValue::from(*v),
+        Value::Array(v) => {
+            let values: Vec<SeaValue> = v.iter().map(to_sea_value).collect();
+            let ty = values.iter().find(|v| v.is_some()).map_or(ArrayType::String, SeaValue::array_type);
+            SeaValue::Array(ty, Some(Box::new(values)))
+        }
+    }
+}
+
+// 将 sea_query 的 Value 转换为参数，空值转换为 NULL，不支持的类型返回 UnsupportedArgs，下标由调用方补充
+pub fn from_sea_value(value: &SeaValue) -> Result<Value, Error> {
+    macro_rules! try_value {
+        ($($sea:ident => $variant:ident),* $(,)?) => {
+            match value {
+                $(
+                    SeaValue::$sea(Some(v)) => return Ok(Value::$variant(v.clone())),
+                    SeaValue::$sea(None) => return Ok(Value::Null),
+                )*
+                _ => {}
+            }
+        };
+    }
+
+    try_value!(
+        Bool => Bool,
+        TinyInt => TinyInt,
+        SmallInt => SmallInt,
+        Int => Int,
+        BigInt => BigInt,
+        TinyUnsigned => TinyUnsigned,
+        SmallUnsigned => SmallUnsigned,
+        Unsigned => Unsigned,
+        BigUnsigned => BigUnsigned,
+        Float => Float,
+        Double => Double,
+        String => String,
+        Bytes => Bytes,
+    );
+
+    #[cfg(feature = "chrono")]
+    try_value!(
+        ChronoDate => ChronoDate,
+        ChronoTime => ChronoTime,
+        ChronoDateTime => ChronoDateTime,
+        ChronoDateTimeUtc => ChronoDateTimeUtc,
+        ChronoDateTimeWithTimeZone => ChronoDateTimeWithTimeZone,
+    );
+
+    #[cfg(feature = "time")]
+    try_value!(
+        TimeDate => TimeDate,
+        TimeTime => TimeTime,
+        TimeDateTime => TimeDateTime,
+        TimeDateTimeWithTimeZone => TimeDateTimeWithTimeZone,
+    );
+
+    #[cfg(feature = "uuid")]
+    try_value!(Uuid => Uuid);
+
+    #[cfg(feature = "rust_decimal")]
+    try_value!(Decimal => Decimal);
+
+    match value {
+        SeaValue::Char(Some(v)) => Ok(Value::String(v.to_string())),
+        SeaValue::Char(None) => Ok(Value::Null),
+        #[cfg(feature = "serde_json")]
+        SeaValue::Json(Some(v)) => Ok(Value::Json(v.as_ref().clone())),
+        #[cfg(feature = "serde_json")]
+        SeaValue::Json(None) => Ok(Value::Null),
+        _ => Err(Error::UnsupportedArgs { index: 0, kind: "sea_query::Value" }),
+    }
+}
+
+// sea_query 中与 Flavor 对应的后端
+#[derive(Clone, Copy)]
+enum SeaBackend {
+    MySQL,
+    PostgreSQL,
+    SQLite,
+}
+
+impl SeaBackend {
+    const ALL: [SeaBackend; 3] = [SeaBackend::MySQL, SeaBackend::PostgreSQL, SeaBackend::SQLite];
+
+    // 返回 Flavor 对应的后端，sea_query 没有对应后端的 Flavor 返回 UnsupportedFlavor
+    fn for_flavor(flavor: &Flavor) -> Result<Self, Error> {
+        match flavor {
+            Flavor::MySQL | Flavor::MySQL57 | Flavor::MariaDB => Ok(SeaBackend::MySQL),
+            Flavor::PostgreSQL => Ok(SeaBackend::PostgreSQL),
+            Flavor::SQLite => Ok(SeaBackend::SQLite),
+            _ => Err(Error::UnsupportedFlavor {
+                flavor: flavor.clone(),
+                target: "sea_query",
+            }),
+        }
+    }
+
+    // 返回后端生成的 SQL 所使用的方言
+    fn dialect(self) -> &'static dyn Dialect {
+        match self {
+            SeaBackend::MySQL => &dialect::MYSQL,
+            SeaBackend::PostgreSQL => &dialect::POSTGRESQL,
+            SeaBackend::SQLite => &dialect::SQLITE,
+        }
+    }
+
+    // 使用后端生成 SQL 字符串，参数保留为占位符
+    fn build<S: QueryStatementBuilder>(self, statement: &S) -> (String, usize) {
+        let (sql, values) = match self {
+            SeaBackend::MySQL => statement.build_any(&MysqlQueryBuilder),
+            SeaBackend::PostgreSQL => statement.build_any(&PostgresQueryBuilder),
+            SeaBackend::SQLite => statement.build_any(&SqliteQueryBuilder),
+        };
+
+        (sql, values.0.len())
+    }
+}
+
+// SeaQuery 将 sea_query 的语句包装为构造器，可以作为其他构造器的参数
+// 编译时使用 Flavor 对应的 sea_query 后端生成 SQL，再将占位符替换为 Flavor 的占位符
+// sea_query 没有对应后端的 Flavor 返回 UnsupportedFlavor
+pub struct SeaQuery<S: QueryStatementBuilder> {
+    statement: S,
+    values: Vec<Value>,
+    flavor: Flavor,
+}
+
+impl<S: QueryStatementBuilder> SeaQuery<S> {
+    // 创建一个新的 SeaQuery 实例，语句中的参数在这里转换，不支持的类型返回 UnsupportedArgs
+    pub fn new(statement: S) -> Result<Self, Error> {
+        let (_, values) = statement.build_any(&PostgresQueryBuilder);
+        let values = values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| from_sea_value(v).map_err(|err| err.at_index(i)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // 提前检查所有后端生成的占位符，保证编译时不会失败
+        for backend in SeaBackend::ALL {
+            let (sql, len) = backend.build(&statement);
+
+            if len != values.len() {
+                return Err(Error::MissingArgs {
+                    expected: len,
+                    got: values.len(),
+                    at: 0,
+                });
+            }
+
+            interpolate_with(backend.dialect(), &sql, len, &mut |_, _| Ok(()))?;
+        }
+
+        Ok(Self {
+            statement,
+            values,
+            flavor: default_flavor(),
+        })
+    }
+
+    // 返回包装的 sea_query 语句
+    pub fn statement(&self) -> &S {
+        &self.statement
+    }
+
+    // 设置 Flavor
+    pub fn set_flavor(&mut self, flavor: Flavor) -> Flavor {
+        std::mem::replace(&mut self.flavor, flavor)
+    }
+}
+
+impl<S: QueryStatementBuilder> Builder for SeaQuery<S> {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: Vec<Value>) -> Result<(String, Vec<Value>), Error> {
+        let backend = SeaBackend::for_flavor(&flavor)?;
+        let (sql, len) = backend.build(&self.statement);
+        let mut values = initial_arg;
+
+        let sql = interpolate_with(backend.dialect(), &sql, len, &mut |buf, i| {
+            values.push(self.values[i].clone());
+            buf.push_str(&flavor.placeholder(values.len()));
+            Ok(())
+        })?;
+
+        Ok((sql, values))
+    }
+
+    fn flavor(&self) -> Flavor {
+        self.flavor.clone()
+    }
+}
+
+impl Expr {
+    // 转换为 sea_query 的表达式，可移植函数按 flavor 改写后再转换
+    // sea_query 没有对应后端的 flavor 返回 UnsupportedFlavor，
+    // 子查询、窗口函数和 FILTER 等 sea_query 无法表示的节点返回 UnsupportedExpr
+    pub fn to_sea_expr(&self, flavor: &Flavor) -> Result<SeaExpr, Error> {
+        SeaBackend::for_flavor(flavor)?;
+        self.to_sea(flavor)
+    }
+
+    fn to_sea(&self, flavor: &Flavor) -> Result<SeaExpr, Error> {
+        let expr = match self {
+            Expr::Column(name) => SeaExpr::col(to_sea_column(name)?),
+            Expr::Value(value) => SeaExpr::Value(to_sea_value(value)),
+            Expr::Raw(sql) => SeaExpr::cust(sql.clone()),
+            Expr::Unary { op, expr } => {
+                let expr = expr.to_sea(flavor)?;
+
+                match op {
+                    UnaryOp::Not => SeaExpr::Unary(UnOper::Not, Box::new(expr)),
+                    UnaryOp::Neg => SeaExpr::cust_with_expr("-($1)", expr),
+                    UnaryOp::IsNull => sea_binary(expr, BinOper::Is, SeaExpr::Keyword(Keyword::Null)),
+                    UnaryOp::IsNotNull => sea_binary(expr, BinOper::IsNot, SeaExpr::Keyword(Keyword::Null)),
+                }
+            }
+            Expr::Binary { op, left, right } => {
+                let op = match op {
+                    BinaryOp::And => BinOper::And,
+                    BinaryOp::Or => BinOper::Or,
+                    BinaryOp::Eq => BinOper::Equal,
+                    BinaryOp::Ne => BinOper::NotEqual,
+                    BinaryOp::Gt => BinOper::GreaterThan,
+                    BinaryOp::Ge => BinOper::GreaterThanOrEqual,
+                    BinaryOp::Lt => BinOper::SmallerThan,
+                    BinaryOp::Le => BinOper::SmallerThanOrEqual,
+                    BinaryOp::Like => BinOper::Like,
+                    BinaryOp::NotLike => BinOper::NotLike,
+                    BinaryOp::In => BinOper::In,
+                    BinaryOp::NotIn => BinOper::NotIn,
+                    BinaryOp::Add => BinOper::Add,
+                    BinaryOp::Sub => BinOper::Sub,
+                    BinaryOp::Mul => BinOper::Mul,
+                    BinaryOp::Div => BinOper::Div,
+                    BinaryOp::Mod => BinOper::Mod,
+                    BinaryOp::Concat => BinOper::Custom("||"),
+                    BinaryOp::Other(op) => BinOper::Custom(op),
+                };
+
+                sea_binary(left.to_sea(flavor)?, op, right.to_sea(flavor)?)
+            }
+            Expr::Between { expr, lower, upper, negated } => {
+                let range = sea_binary(lower.to_sea(flavor)?, BinOper::And, upper.to_sea(flavor)?);
+                let op = if *negated { BinOper::NotBetween } else { BinOper::Between };
+                sea_binary(expr.to_sea(flavor)?, op, range)
+            }
+            Expr::List(exprs) => SeaExpr::Tuple(exprs.iter().map(|e| e.to_sea(flavor)).collect::<Result<_, _>>()?),
+            Expr::Func { name, args } => {
+                let args = args.iter().map(|e| e.to_sea(flavor)).collect::<Result<Vec<_>, _>>()?;
+                SeaExpr::FunctionCall(Func::cust(Alias::new(name)).args(args))
+            }
+            // 转换后无法再改写，不支持的特性总是返回错误
+            Expr::Call { func, args } => func.lower(flavor, BuildMode::Strict, args)?.to_sea(flavor)?,
+            Expr::Case { operand, whens, otherwise } => {
+                let otherwise = otherwise.as_ref().map(|e| e.to_sea(flavor)).transpose()?;
+
+                if whens.is_empty() {
+                    return Ok(otherwise.unwrap_or(SeaExpr::Keyword(Keyword::Null)));
+                }
+
+                // CASE operand WHEN value 等价于 CASE WHEN operand = value
+                let operand = operand.as_ref().map(|e| e.to_sea(flavor)).transpose()?;
+                let mut case = CaseStatement::new();
+
+                for (when, then) in whens {
+                    let when = match &operand {
+                        Some(operand) => sea_binary(operand.clone(), BinOper::Equal, when.to_sea(flavor)?),
+                        None => when.to_sea(flavor)?,
+                    };
+                    case = case.case(when, then.to_sea(flavor)?);
+                }
+
+                if let Some(otherwise) = otherwise {
+                    case = case.finally(otherwise);
+                }
+
+                SeaExpr::Case(Box::new(case))
+            }
+            Expr::Cast { expr, ty } => SeaExpr::FunctionCall(Func::cast_as(expr.to_sea(flavor)?, Alias::new(ty))),
+            Expr::Subquery(_) | Expr::TableFunc { .. } => return Err(Error::UnsupportedExpr { kind: "subquery" }),
+            Expr::Over { .. } => return Err(Error::UnsupportedExpr { kind: "OVER" }),
+            Expr::Filter { .. } => return Err(Error::UnsupportedExpr { kind: "FILTER" }),
+            Expr::Clause { expr, keyword, arg } => {
+                SeaExpr::cust_with_exprs(format!("$1 {} $2", keyword), [expr.to_sea(flavor)?, arg.to_sea(flavor)?])
+            }
+        };
+
+        Ok(expr)
+    }
+
+    // 从 sea_query 的表达式转换，无法表示的节点返回 UnsupportedExpr，不支持的参数返回 UnsupportedArgs
+    pub fn from_sea_expr(expr: &SeaExpr) -> Result<Expr, Error> {
+        let expr = match expr {
+            SeaExpr::Column(column) => Expr::Column(from_sea_column(column)?),
+            SeaExpr::Tuple(exprs) => Expr::List(exprs.iter().map(Expr::from_sea_expr).collect::<Result<_, _>>()?),
+            SeaExpr::Unary(UnOper::Not, expr) => Expr::from_sea_expr(expr)?.unary(UnaryOp::Not),
+            SeaExpr::Binary(left, op, right) => from_sea_binary(left, *op, right)?,
+            SeaExpr::FunctionCall(call) => from_sea_call(call)?,
+            SeaExpr::Value(value) | SeaExpr::Constant(value) => Expr::Value(from_sea_value(value)?),
+            SeaExpr::Values(values) => Expr::List(
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(i, v)| from_sea_value(v).map(Expr::Value).map_err(|err| err.at_index(i)))
+                    .collect::<Result<_, _>>()?,
+            ),
+            SeaExpr::Custom(sql) => Expr::Raw(sql.to_string()),
+            SeaExpr::Keyword(keyword) => match keyword {
+                Keyword::Null => Expr::Value(Value::Null),
+                Keyword::CurrentDate => Expr::raw("CURRENT_DATE"),
+                Keyword::CurrentTime => Expr::raw("CURRENT_TIME"),
+                Keyword::CurrentTimestamp => Expr::raw("CURRENT_TIMESTAMP"),
+                Keyword::Default => Expr::raw("DEFAULT"),
+                Keyword::Custom(iden) => Expr::Raw(iden.to_string()),
+                _ => return Err(Error::UnsupportedExpr { kind: "sea_query::Keyword" }),
+            },
+            SeaExpr::SubQuery(..) => return Err(Error::UnsupportedExpr { kind: "subquery" }),
+            SeaExpr::Case(_) => return Err(Error::UnsupportedExpr { kind: "CASE" }),
+            _ => return Err(Error::UnsupportedExpr { kind: "sea_query::Expr" }),
+        };
+
+        Ok(expr)
+    }
+}
+
+fn sea_binary(left: SeaExpr, op: BinOper, right: SeaExpr) -> SeaExpr {
+    SeaExpr::Binary(Box::new(left), op, Box::new(right))
+}
+
+fn sea_iden(name: &str) -> DynIden {
+    Alias::new(name).into_iden()
+}
+
+// 将 table.column 形式的列名转换为 sea_query 的列引用，最多支持 database.schema.table.column
+fn to_sea_column(name: &str) -> Result<ColumnRef, Error> {
+    let mut parts: Vec<&str> = name.split('.').collect();
+    let column = parts.pop().unwrap_or_default();
+
+    let table = match parts.as_slice() {
+        [] => None,
+        [table] => Some(TableName(None, sea_iden(table))),
+        [schema, table] => Some(TableName(Some(SchemaName(None, sea_iden(schema))), sea_iden(table))),
+        [database, schema, table] => Some(TableName(
+            Some(SchemaName(Some(DatabaseName(sea_iden(database))), sea_iden(schema))),
+            sea_iden(table),
+        )),
+        _ => return Err(Error::InvalidIdentifier { name: name.to_string() }),
+    };
+
+    Ok(match column {
+        "*" => ColumnRef::Asterisk(table),
+        _ => ColumnRef::Column(ColumnName(table, sea_iden(column))),
+    })
+}
+
+// 将 sea_query 的列引用转换为 table.column 形式的列名
+fn from_sea_column(column: &ColumnRef) -> Result<String, Error> {
+    let (table, column) = match column {
+        ColumnRef::Column(ColumnName(table, column)) => (table, column.to_string()),
+        ColumnRef::Asterisk(table) => (table, "*".to_string()),
+        _ => return Err(Error::UnsupportedExpr { kind: "sea_query::ColumnRef" }),
+    };
+
+    let mut parts = Vec::new();
+
+    if let Some(TableName(schema, table)) = table {
+        if let Some(SchemaName(database, schema)) = schema {
+            if let Some(DatabaseName(database)) = database {
+                parts.push(database.to_string());
+            }
+
+            parts.push(schema.to_string());
+        }
+
+        parts.push(table.to_string());
+    }
+
+    parts.push(column);
+    Ok(parts.join("."))
+}
+
+fn from_sea_binary(left: &SeaExpr, op: BinOper, right: &SeaExpr) -> Result<Expr, Error> {
+    let left_expr = Expr::from_sea_expr(left)?;
+
+    // IS NULL、BETWEEN 在 sea_query 中是二元运算，在 Expr 中有单独的节点
+    match (op, right) {
+        (BinOper::Is, SeaExpr::Keyword(Keyword::Null)) => return Ok(left_expr.is_null()),
+        (BinOper::IsNot, SeaExpr::Keyword(Keyword::Null)) => return Ok(left_expr.is_not_null()),
+        (BinOper::Between | BinOper::NotBetween, SeaExpr::Binary(lower, BinOper::And, upper)) => {
+            let lower = Expr::from_sea_expr(lower)?;
+            let upper = Expr::from_sea_expr(upper)?;
+
+            return Ok(match op {
+                BinOper::Between => left_expr.between(lower, upper),
+                _ => left_expr.not_between(lower, upper),
+            });
+        }
+        _ => {}
+    }
+
+    let op = match op {
+        BinOper::And => BinaryOp::And,
+        BinOper::Or => BinaryOp::Or,
+        BinOper::Like => BinaryOp::Like,
+        BinOper::NotLike => BinaryOp::NotLike,
+        BinOper::In => BinaryOp::In,
+        BinOper::NotIn => BinaryOp::NotIn,
+        BinOper::Equal => BinaryOp::Eq,
+        BinOper::NotEqual => BinaryOp::Ne,
+        BinOper::SmallerThan => BinaryOp::Lt,
+        BinOper::GreaterThan => BinaryOp::Gt,
+        BinOper::SmallerThanOrEqual => BinaryOp::Le,
+        BinOper::GreaterThanOrEqual => BinaryOp::Ge,
+        BinOper::Add => BinaryOp::Add,
+        BinOper::Sub => BinaryOp::Sub,
+        BinOper::Mul => BinaryOp::Mul,
+        BinOper::Div => BinaryOp::Div,
+        BinOper::Mod => BinaryOp::Mod,
+        BinOper::Is => BinaryOp::Other("IS"),
+        BinOper::IsNot => BinaryOp::Other("IS NOT"),
+        BinOper::Custom("||") => BinaryOp::Concat,
+        BinOper::Custom(op) => BinaryOp::Other(op),
+        _ => return Err(Error::UnsupportedExpr { kind: "sea_query::BinOper" }),
+    };
+
+    Ok(left_expr.binary(op, Expr::from_sea_expr(right)?))
+}
+
+fn from_sea_call(call: &FunctionCall) -> Result<Expr, Error> {
+    if call.get_mods().iter().any(|m| m.distinct) || call.get_filter().is_some() {
+        return Err(Error::UnsupportedExpr { kind: "sea_query::FunctionCall" });
+    }
+
+    let args = call.get_args();
+
+    let name = match call.get_func() {
+        // CAST 的参数是 expr AS type
+        Func::Cast => {
+            return match args {
+                [SeaExpr::Binary(expr, BinOper::As, ty)] => {
+                    let ty = match ty.as_ref() {
+                        SeaExpr::Custom(ty) => ty.to_string(),
+                        SeaExpr::TypeName(ty) => ty.1.to_string(),
+                        _ => return Err(Error::UnsupportedExpr { kind: "CAST" }),
+                    };
+                    Ok(Expr::from_sea_expr(expr)?.cast(&ty))
+                }
+                _ => Err(Error::UnsupportedExpr { kind: "CAST" }),
+            };
+        }
+        // 各后端的写法不同，转换为可移植的函数
+        Func::IfNull => {
+            return Ok(Expr::Call {
+                func: Function::IfNull,
+                args: args.iter().map(Expr::from_sea_expr).collect::<Result<_, _>>()?,
+            });
+        }
+        Func::Custom(name) => name.to_string(),
+        Func::Max => "MAX".to_string(),
+        Func::Min => "MIN".to_string(),
+        Func::Sum => "SUM".to_string(),
+        Func::Avg => "AVG".to_string(),
+        Func::Abs => "ABS".to_string(),
+        Func::Count => "COUNT".to_string(),
+        Func::Greatest => "GREATEST".to_string(),
+        Func::Least => "LEAST".to_string(),
+        Func::CharLength => "CHAR_LENGTH".to_string(),
+        Func::Coalesce => "COALESCE".to_string(),
+        Func::Lower => "LOWER".to_string(),
+        Func::Upper => "UPPER".to_string(),
+        Func::Round => "ROUND".to_string(),
+        _ => return Err(Error::UnsupportedExpr { kind: "sea_query::Func" }),
+    };
+
+    Ok(Expr::Func {
+        name,
+        args: args.iter().map(Expr::from_sea_expr).collect::<Result<_, _>>()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::RawBuilder;
+    use ::sea_query::{ExprTrait, Query};
+
+    #[test]
+    fn test_from_sea_query() {
+        let select = Query::select()
+            .column(Alias::new("name"))
+            .from(Alias::new("user"))
+            .and_where(SeaExpr::col(Alias::new("id")).eq(5))
+            .and_where(SeaExpr::col(Alias::new("name")).like("a?%"))
+            .and_where(SeaExpr::col(Alias::new("deleted_at")).is_null())
+            .to_owned();
+        let query = SeaQuery::new(select).unwrap();
+
+        let (sql, args) = query.build_with_flavor(Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            r#"SELECT "name" FROM "user" WHERE "id" = $1 AND "name" LIKE $2 AND "deleted_at" IS NULL"#
+        );
+        assert_eq!(args, vec![Value::Int(5), Value::String("a?%".to_string())]);
+
+        let (sql, args) = query.build_with_flavor(Flavor::MySQL, vec![Value::Int(1)]).unwrap();
+        assert_eq!(sql, "SELECT `name` FROM `user` WHERE `id` = ? AND `name` LIKE ? AND `deleted_at` IS NULL");
+        assert_eq!(args.len(), 3);
+
+        // sea_query 没有 SQL Server 后端，不能按 PostgreSQL 的写法生成
+        assert!(matches!(
+            query.build_with_flavor(Flavor::SQLServer, Vec::new()),
+            Err(Error::UnsupportedFlavor { flavor: Flavor::SQLServer, target: "sea_query" })
+        ));
+    }
+
+    #[test]
+    fn test_to_sea_expr() {
+        let expr = Expr::col("users.id")
+            .in_([1, 2])
+            .and(Expr::col("name").like("a%").or(Expr::col("deleted_at").is_null()))
+            .and(Expr::col("age").between(18, 30))
+            .and(Expr::Call { func: Function::IfNull, args: vec![Expr::col("score"), Expr::val(0)] }.gt(60));
+
+        let select = Query::select()
+            .expr(SeaExpr::col(Alias::new("id")))
+            .from(Alias::new("users"))
+            .and_where(expr.to_sea_expr(&Flavor::PostgreSQL).unwrap())
+            .to_owned();
+        let (sql, values) = select.build(PostgresQueryBuilder);
+        assert_eq!(
+            sql,
+            r#"SELECT "id" FROM "users" WHERE "users"."id" IN ($1, $2) AND ("name" LIKE $3 OR "deleted_at" IS NULL) AND ("age" BETWEEN $4 AND $5) AND COALESCE("score", $6) > $7"#
+        );
+        assert_eq!(values.0.len(), 7);
+
+        let expr = Expr::col("price").cast("DECIMAL(10, 2)").binary(BinaryOp::Mul, Expr::col("qty"));
+        let select = Query::select().expr(expr.to_sea_expr(&Flavor::MySQL).unwrap()).to_owned();
+        assert_eq!(select.to_string(MysqlQueryBuilder), "SELECT CAST(`price` AS DECIMAL(10, 2)) * `qty`");
+
+        assert!(matches!(
+            expr.to_sea_expr(&Flavor::Oracle),
+            Err(Error::UnsupportedFlavor { flavor: Flavor::Oracle, target: "sea_query" })
+        ));
+        assert!(matches!(
+            Expr::subquery(RawBuilder::new(|_| "SELECT 1".to_string())).to_sea_expr(&Flavor::MySQL),
+            Err(Error::UnsupportedExpr { kind: "subquery" })
+        ));
+    }
+
+    #[test]
+    fn test_from_sea_expr() {
+        let sea = SeaExpr::col((Alias::new("users"), Alias::new("id")))
+            .is_in([1, 2])
+            .and(SeaExpr::col(Alias::new("age")).not_between(18, 30))
+            .and(SeaExpr::col(Alias::new("deleted_at")).is_not_null());
+        let expr = Expr::from_sea_expr(&sea).unwrap();
+
+        assert_eq!(
+            expr,
+            Expr::col("users.id")
+                .in_([1, 2])
+                .and(Expr::col("age").not_between(18, 30))
+                .and(Expr::col("deleted_at").is_not_null())
+        );
+
+        // 往返转换后与 sea_query 直接生成的 SQL 相同
+        let select = Query::select().and_where(sea.clone()).to_owned();
+        let round_trip = Query::select().and_where(expr.to_sea_expr(&Flavor::SQLite).unwrap()).to_owned();
+        assert_eq!(round_trip.to_string(SqliteQueryBuilder), select.to_string(SqliteQueryBuilder));
+
+        let sea = SeaExpr::col(Alias::new("name")).cast_as(Alias::new("TEXT"));
+        assert_eq!(Expr::from_sea_expr(&sea).unwrap(), Expr::col("name").cast("TEXT"));
+
+        let sea = SeaExpr::case(SeaExpr::col(Alias::new("a")).eq(1), "one").into();
+        assert!(matches!(Expr::from_sea_expr(&sea), Err(Error::UnsupportedExpr { kind: "CASE" })));
+    }
+}
@@ -0,0 +1,249 @@
+use crate::builder::BuildMode;
+use crate::dialect::Feature;
+use crate::error::Error;
+use crate::expr::{BinaryOp, Expr, UnaryOp};
+use crate::flavor::Flavor;
+use crate::func::{call, Function};
+
+// 全文检索的查询模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    // 自然语言查询：MySQL 的 NATURAL LANGUAGE MODE、PostgreSQL 的 plainto_tsquery、SQL Server 的 FREETEXT
+    Natural,
+    // 支持 +、-、引号等运算符的查询：MySQL 的 BOOLEAN MODE、PostgreSQL 的 websearch_to_tsquery、SQL Server 的 CONTAINS
+    Boolean,
+}
+
+// 返回 columns 匹配 query 的全文检索条件，query 绑定为参数
+// SQLite 要求 columns 是 FTS5 虚拟表的列，Oracle 要求列上有 CONTEXT 索引
+// SQLite 的多个列展开为 OR 连接的 MATCH，不能再与同一查询中的其他 MATCH 条件用 AND 组合
+// 不支持全文检索的方言按 BuildMode 处理：严格模式返回 UnsupportedFeature，宽松模式生成 MySQL 的写法
+pub fn match_against<E: Into<Expr>>(
+    columns: impl IntoIterator<Item = E>,
+    query: impl Into<Expr>,
+    mode: SearchMode,
+) -> Expr {
+    call(Function::Match(mode), with_query(columns, query))
+}
+
+// 返回 columns 与 query 的相关度，值越大越相关，可以用于查询的列和 ORDER BY
+// SQLite 使用 FTS5 的 rank 列，忽略 columns 和 query，查询中必须有同一个表的 match_against 条件
+// SQL Server 和 Oracle 的相关度需要 FREETEXTTABLE 或 SCORE 标签，不支持的方言按 BuildMode 处理
+pub fn match_rank<E: Into<Expr>>(
+    columns: impl IntoIterator<Item = E>,
+    query: impl Into<Expr>,
+    mode: SearchMode,
+) -> Expr {
+    call(Function::MatchRank(mode), with_query(columns, query))
+}
+
+// 参数的最后一个是查询字符串
+fn with_query<E: Into<Expr>>(columns: impl IntoIterator<Item = E>, query: impl Into<Expr>) -> Vec<Expr> {
+    let mut args: Vec<Expr> = columns.into_iter().map(Into::into).collect();
+    args.push(query.into());
+    args
+}
+
+fn mysql_match(mode: SearchMode, columns: &[Expr], query: &Expr) -> Expr {
+    let modifier = match mode {
+        SearchMode::Natural => "NATURAL LANGUAGE MODE",
+        SearchMode::Boolean => "BOOLEAN MODE",
+    };
+    let against = query.clone().binary(BinaryOp::Other("IN"), Expr::raw(modifier));
+    Expr::func("MATCH", columns.to_vec()).binary(BinaryOp::Other("AGAINST"), Expr::List(vec![against]))
+}
+
+// 返回 PostgreSQL 的 tsvector 和 tsquery，多个列用空格连接后检索
+fn postgres_operands(mode: SearchMode, columns: &[Expr], query: &Expr) -> (Expr, Expr) {
+    let document = match columns {
+        [column] => column.clone(),
+        _ => Expr::func("concat_ws", [Expr::raw("' '")].into_iter().chain(columns.iter().cloned())),
+    };
+    let parser = match mode {
+        SearchMode::Natural => "plainto_tsquery",
+        SearchMode::Boolean => "websearch_to_tsquery",
+    };
+    (Expr::func("to_tsvector", [document]), Expr::func(parser, [query.clone()]))
+}
+
+// 对每个列分别检索，任意一列匹配即可
+// 结果可能与外层的字符串直接拼接，多个列时加上括号
+fn any_column(columns: &[Expr], matches: impl Fn(Expr) -> Expr) -> Expr {
+    match columns {
+        [column] => matches(column.clone()),
+        _ => {
+            let any = columns.iter().cloned().map(matches).reduce(|left, right| left.or(right));
+            Expr::List(any.into_iter().collect())
+        }
+    }
+}
+
+// Flavor 不支持全文检索时，严格模式返回 UnsupportedFeature，宽松模式生成 MySQL 的 MATCH ... AGAINST
+pub(crate) fn lower_match(
+    flavor: &Flavor,
+    build_mode: BuildMode,
+    mode: SearchMode,
+    columns: &[Expr],
+    query: &Expr,
+) -> Result<Expr, Error> {
+    flavor.check_feature(Feature::FullTextSearch, build_mode)?;
+
+    let expr = match flavor {
+        Flavor::PostgreSQL => {
+            let (vector, tsquery) = postgres_operands(mode, columns, query);
+            vector.binary(BinaryOp::Other("@@"), tsquery)
+        }
+        // FTS5 的查询字符串总是按 FTS5 的查询语法解析，两种模式相同
+        Flavor::SQLite => any_column(columns, |column| column.binary(BinaryOp::Other("MATCH"), query.clone())),
+        Flavor::SQLServer => {
+            let name = match mode {
+                SearchMode::Natural => "FREETEXT",
+                SearchMode::Boolean => "CONTAINS",
+            };
+            let target = match columns {
+                [column] => column.clone(),
+                _ => Expr::List(columns.to_vec()),
+            };
+            Expr::func(name, [target, query.clone()])
+        }
+        Flavor::Oracle => any_column(columns, |column| {
+            Expr::func("CONTAINS", [column, query.clone()]).gt(Expr::raw("0"))
+        }),
+        _ => mysql_match(mode, columns, query),
+    };
+
+    Ok(expr)
+}
+
+// Flavor 不支持相关度时，严格模式返回 UnsupportedFeature，宽松模式生成 MySQL 的 MATCH ... AGAINST
+pub(crate) fn lower_rank(
+    flavor: &Flavor,
+    build_mode: BuildMode,
+    mode: SearchMode,
+    columns: &[Expr],
+    query: &Expr,
+) -> Result<Expr, Error> {
+    flavor.check_feature(Feature::FullTextRank, build_mode)?;
+
+    let expr = match flavor {
+        Flavor::PostgreSQL => {
+            let (vector, tsquery) = postgres_operands(mode, columns, query);
+            Expr::func("ts_rank", [vector, tsquery])
+        }
+        // FTS5 的 rank 是虚拟表的隐藏列，由同一个查询中的 MATCH 条件决定，columns 和 query 都不参与计算
+        // 因此只能用在带有 match_against 条件的 FTS5 查询中，越小越相关，取反后与其他方言一致
+        Flavor::SQLite => Expr::raw("rank").unary(UnaryOp::Neg),
+        // MATCH ... AGAINST 用在条件以外的地方时返回相关度
+        _ => mysql_match(mode, columns, query),
+    };
+
+    Ok(expr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::args::Args;
+    use crate::cond::Cond;
+    use crate::value::Value;
+
+    #[test]
+    fn test_search() {
+        let mut args = Args::new();
+        let mut cond = Cond::new(&mut args);
+        let matched = cond.match_against(&["title", "body"], "rust orm", SearchMode::Boolean);
+        let format = format!(
+            "SELECT id, {} FROM posts WHERE {} ORDER BY 2 DESC",
+            args.add_expr(match_rank([Expr::raw("title"), Expr::raw("body")], "rust orm", SearchMode::Natural)),
+            matched
+        );
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::MySQL, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT id, MATCH(title, body) AGAINST (? IN NATURAL LANGUAGE MODE) FROM posts \
+             WHERE MATCH(title, body) AGAINST (? IN BOOLEAN MODE) ORDER BY 2 DESC"
+        );
+        assert_eq!(values, vec![Value::String("rust orm".to_string()); 2]);
+
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT id, ts_rank(to_tsvector(concat_ws(' ', title, body)), plainto_tsquery($1)) FROM posts \
+             WHERE to_tsvector(concat_ws(' ', title, body)) @@ websearch_to_tsquery($2) ORDER BY 2 DESC"
+        );
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::SQLite, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT id, -(rank) FROM posts WHERE (title MATCH ? OR body MATCH ?) ORDER BY 2 DESC"
+        );
+        assert_eq!(values.len(), 2);
+
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::SQLServer, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT id, MATCH(title, body) AGAINST (@p1 IN NATURAL LANGUAGE MODE) FROM posts \
+             WHERE CONTAINS((title, body), @p2) ORDER BY 2 DESC"
+        );
+
+        args.mode = BuildMode::Strict;
+        let err = args.compile_with_flavor(&format, Flavor::SQLServer, Vec::new()).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedFeature { feature: Feature::FullTextRank, .. }));
+
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::MySQL57, Vec::new()).unwrap();
+        assert!(sql.starts_with("SELECT id, MATCH(title, body) AGAINST (? IN NATURAL LANGUAGE MODE)"));
+
+        let mut args = Args::new();
+        let natural = args.add_expr(match_against([Expr::col("body")], "rust", SearchMode::Natural));
+        let boolean = args.add_expr(match_against([Expr::col("title"), Expr::col("body")], "rust", SearchMode::Boolean));
+        let format = format!("{} AND {}", natural, boolean);
+
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::SQLServer, Vec::new()).unwrap();
+        assert_eq!(sql, "FREETEXT(body, @p1) AND CONTAINS((title, body), @p2)");
+
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::Oracle, Vec::new()).unwrap();
+        assert_eq!(sql, "CONTAINS(body, :1) > 0 AND (CONTAINS(title, :2) > 0 OR CONTAINS(body, :3) > 0)");
+
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::ClickHouse, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            "MATCH(body) AGAINST (? IN NATURAL LANGUAGE MODE) AND MATCH(title, body) AGAINST (? IN BOOLEAN MODE)"
+        );
+
+        args.mode = BuildMode::Strict;
+        let err = args.compile_with_flavor(&format, Flavor::ClickHouse, Vec::new()).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedFeature { feature: Feature::FullTextSearch, .. }));
+    }
+
+    #[cfg(feature = "rusqlite")]
+    #[test]
+    fn test_search_sqlite() {
+        let conn = ::rusqlite::Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE VIRTUAL TABLE posts USING fts5(title, body);
+             INSERT INTO posts (rowid, title, body) VALUES
+                 (1, 'rust orm', 'query builder'),
+                 (2, 'go', 'a rust crate'),
+                 (3, 'python', 'nothing here');",
+        )
+        .unwrap();
+
+        // 多个列的 MATCH 用 OR 连接，任意一列匹配即可，相关度来自 FTS5 的 rank
+        let mut args = Args::new();
+        let columns = [Expr::col("title"), Expr::col("body")];
+        let format = format!(
+            "SELECT rowid, {} FROM posts WHERE {} ORDER BY 2 DESC",
+            args.add_expr(match_rank(columns.clone(), "rust", SearchMode::Natural)),
+            args.add_expr(match_against(columns, "rust", SearchMode::Boolean)),
+        );
+
+        let (sql, params) = args.compile_rusqlite(&format).unwrap();
+        assert_eq!(sql, "SELECT rowid, -(rank) FROM posts WHERE (title MATCH ? OR body MATCH ?) ORDER BY 2 DESC");
+
+        let mut stmt = conn.prepare(&sql).unwrap();
+        let rows = stmt.query_map(params, |row| row.get::<_, i64>(0)).unwrap();
+        let ids: Vec<i64> = rows.collect::<Result<_, _>>().unwrap();
+        assert_eq!(ids, vec![1, 2]);
+    }
+}
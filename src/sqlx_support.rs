@@ -0,0 +1,350 @@
+use std::marker::PhantomData;
+
+use ::sqlx::query::{Query, QueryAs};
+use ::sqlx::{Database, FromRow};
+
+use crate::builder::Builder;
+use crate::error::Error;
+use crate::flavor::Flavor;
+use crate::value::Value;
+
+// SqlxDatabase 将 sqlx 的数据库类型与 Flavor 对应起来，并负责绑定参数
+pub trait SqlxDatabase: Database {
+    // 返回编译 SQL 时使用的 Flavor
+    fn flavor() -> Flavor;
+
+    // 将一个参数绑定到查询
+    fn bind<'q>(
+        query: Query<'q, Self, <Self as Database>::Arguments<'q>>,
+        value: &Value,
+    ) -> Query<'q, Self, <Self as Database>::Arguments<'q>>;
+
+    // 将一个参数绑定到映射为 O 的查询
+    fn bind_as<'q, O>(
+        query: QueryAs<'q, Self, O, <Self as Database>::Arguments<'q>>,
+        value: &Value,
+    ) -> QueryAs<'q, Self, O, <Self as Database>::Arguments<'q>>;
+}
+
+// PostgreSQL 中不指定类型的 NULL
+// 参数的类型 OID 为 0，由服务端根据上下文推断，因此可以写入任意类型的列
+#[cfg(feature = "sqlx-postgres")]
+struct UntypedNull;
+
+#[cfg(feature = "sqlx-postgres")]
+impl ::sqlx::Type<::sqlx::Postgres> for UntypedNull {
+    fn type_info() -> ::sqlx::postgres::PgTypeInfo {
+        ::sqlx::postgres::PgTypeInfo::with_oid(::sqlx::postgres::types::Oid(0))
+    }
+}
+
+#[cfg(feature = "sqlx-postgres")]
+impl ::sqlx::Encode<'_, ::sqlx::Postgres> for UntypedNull {
+    fn encode_by_ref(
+        &self,
+        _buf: &mut ::sqlx::postgres::PgArgumentBuffer,
+    ) -> Result<::sqlx::encode::IsNull, ::sqlx::error::BoxDynError> {
+        Ok(::sqlx::encode::IsNull::Yes)
+    }
+}
+
+// 将一个 Value 绑定到查询
+// 整数统一按有符号类型绑定，超出 i64 范围的无符号整数在 SqlxQuery::new 中已经拒绝
+// 不支持 Decimal 的数据库以文本形式绑定 Decimal，只有 PostgreSQL 可以绑定数组
+// PostgreSQL 的 NULL 不指定类型，其他数据库的 NULL 与类型无关，按文本绑定
+macro_rules! bind_value {
+    (@null untyped, $query:ident) => {
+        $query.bind(UntypedNull)
+    };
+    (@null text, $query:ident) => {
+        $query.bind(None::<String>)
+    };
+    (@decimal native, $query:ident, $v:ident) => {
+        $query.bind(*$v)
+    };
+    (@decimal text, $query:ident, $v:ident) => {
+        $query.bind($v.to_string())
+    };
+    (@array native, $query:ident, $v:ident) => {
+        bind_array!(
+            $query,
+            $v,
+            Bool(v) => *v,
+            TinyInt(v) => i16::from(*v),
+            SmallInt(v) => *v,
+            Int(v) => *v,
+            BigInt(v) => *v,
+            TinyUnsigned(v) => i16::from(*v),
+            SmallUnsigned(v) => i32::from(*v),
+            Unsigned(v) => i64::from(*v),
+            BigUnsigned(v) => *v as i64,
+            Float(v) => *v,
+            Double(v) => *v,
+            String(v) => v.clone(),
+            Bytes(v) => v.clone(),
+            #[cfg(feature = "chrono")]
+            ChronoDate(v) => *v,
+            #[cfg(feature = "chrono")]
+            ChronoTime(v) => *v,
+            #[cfg(feature = "chrono")]
+            ChronoDateTime(v) => *v,
+            #[cfg(feature = "chrono")]
+            ChronoDateTimeUtc(v) => *v,
+            #[cfg(feature = "chrono")]
+            ChronoDateTimeWithTimeZone(v) => v.with_timezone(&chrono::Utc),
+            #[cfg(feature = "time")]
+            TimeDate(v) => *v,
+            #[cfg(feature = "time")]
+            TimeTime(v) => *v,
+            #[cfg(feature = "time")]
+            TimeDateTime(v) => *v,
+            #[cfg(feature = "time")]
+            TimeDateTimeWithTimeZone(v) => *v,
+            #[cfg(feature = "uuid")]
+            Uuid(v) => *v,
+            #[cfg(feature = "serde_json")]
+            Json(v) => v.clone(),
+            #[cfg(feature = "rust_decimal")]
+            Decimal(v) => *v,
+        )
+    };
+    // 不支持数组的数据库在 SqlxQuery::new 中已经拒绝
+    (@array unsupported, $query:ident, $v:ident) => {{
+        let _ = $v;
+        $query.bind(None::<String>)
+    }};
+    ($query:ident, $value:ident, $null:ident, $decimal:ident, $array:ident) => {
+        match $value {
+            Value::Null => bind_value!(@null $null, $query),
+            Value::Bool(v) => $query.bind(*v),
+            Value::TinyInt(v) => $query.bind(i16::from(*v)),
+            Value::SmallInt(v) => $query.bind(*v),
+            Value::Int(v) => $query.bind(*v),
+            Value::BigInt(v) => $query.bind(*v),
+            Value::TinyUnsigned(v) => $query.bind(i16::from(*v)),
+            Value::SmallUnsigned(v) => $query.bind(i32::from(*v)),
+            Value::Unsigned(v) => $query.bind(i64::from(*v)),
+            Value::BigUnsigned(v) => $query.bind(*v as i64),
+            Value::Float(v) => $query.bind(*v),
+            Value::Double(v) => $query.bind(*v),
+            Value::String(v) => $query.bind(v.clone()),
+            Value::Bytes(v) => $query.bind(v.clone()),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDate(v) => $query.bind(*v),
+            #[cfg(feature = "chrono")]
+            Value::ChronoTime(v) => $query.bind(*v),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDateTime(v) => $query.bind(*v),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDateTimeUtc(v) => $query.bind(*v),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDateTimeWithTimeZone(v) => $query.bind(v.with_timezone(&chrono::Utc)),
+            #[cfg(feature = "time")]
+            Value::TimeDate(v) => $query.bind(*v),
+            #[cfg(feature = "time")]
+            Value::TimeTime(v) => $query.bind(*v),
+            #[cfg(feature = "time")]
+            Value::TimeDateTime(v) => $query.bind(*v),
+            #[cfg(feature = "time")]
+            Value::TimeDateTimeWithTimeZone(v) => $query.bind(*v),
+            #[cfg(feature = "uuid")]
+            Value::Uuid(v) => $query.bind(*v),
+            #[cfg(feature = "serde_json")]
+            Value::Json(v) => $query.bind(v.clone()),
+            #[cfg(feature = "rust_decimal")]
+            Value::Decimal(v) => bind_value!(@decimal $decimal, $query, v),
+            Value::Array(v) => bind_value!(@array $array, $query, v),
+        }
+    };
+}
+
+// 将数组按第一个非 NULL 元素的类型绑定为 Vec<Option<T>>，其他元素与它的类型相同或者为 NULL
+// 所有元素都是 NULL 时按文本数组绑定
+#[cfg(feature = "sqlx-postgres")]
+macro_rules! bind_array {
+    ($query:ident, $values:ident, $($(#[$meta:meta])* $variant:ident($v:ident) => $convert:expr),* $(,)?) => {
+        match $values.iter().find(|v| !v.is_null()) {
+            $(
+                $(#[$meta])*
+                Some(Value::$variant(_)) => $query.bind(
+                    $values
+                        .iter()
+                        .map(|v| match v {
+                            Value::$variant($v) => Some($convert),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>(),
+                ),
+            )*
+            _ => $query.bind(vec![None::<String>; $values.len()]),
+        }
+    };
+}
+
+// 为 sqlx 的数据库类型实现 SqlxDatabase
+macro_rules! impl_sqlx_database {
+    ($feature:literal, $db:ty, $flavor:expr, $null:ident, $decimal:ident, $array:ident) => {
+        #[cfg(feature = $feature)]
+        impl SqlxDatabase for $db {
+            fn flavor() -> Flavor {
+                $flavor
+            }
+
+            fn bind<'q>(
+                query: Query<'q, Self, <Self as Database>::Arguments<'q>>,
+                value: &Value,
+            ) -> Query<'q, Self, <Self as Database>::Arguments<'q>> {
+                bind_value!(query, value, $null, $decimal, $array)
+            }
+
+            fn bind_as<'q, O>(
+                query: QueryAs<'q, Self, O, <Self as Database>::Arguments<'q>>,
+                value: &Value,
+            ) -> QueryAs<'q, Self, O, <Self as Database>::Arguments<'q>> {
+                bind_value!(query, value, $null, $decimal, $array)
+            }
+        }
+    };
+}
+
+impl_sqlx_database!("sqlx-postgres", ::sqlx::Postgres, Flavor::PostgreSQL, untyped, native, native);
+impl_sqlx_database!("sqlx-mysql", ::sqlx::MySql, Flavor::MySQL, text, native, unsupported);
+impl_sqlx_database!("sqlx-sqlite", ::sqlx::Sqlite, Flavor::SQLite, text, text, unsupported);
+
+// SqlxQuery 保存编译后的 SQL 和参数
+// sqlx 的查询借用 SQL 字符串，因此由 SqlxQuery 持有 SQL，再通过 query 或 query_as 生成查询
+pub struct SqlxQuery<DB: SqlxDatabase> {
+    sql: String,
+    values: Vec<Value>,
+    database: PhantomData<DB>,
+}
+
+impl<DB: SqlxDatabase> SqlxQuery<DB> {
+    // 使用编译后的 SQL 和参数创建 SqlxQuery，超出 i64 范围的无符号整数返回 UnsupportedArgs
+    // 数组只能绑定到 PostgreSQL，非 NULL 元素的类型必须相同，否则同样返回 UnsupportedArgs
+    pub fn new(sql: String, values: Vec<Value>) -> Result<Self, Error> {
+        if let Some(index) = values.iter().position(|v| matches!(v, Value::BigUnsigned(v) if i64::try_from(*v).is_err())) {
+            return Err(Error::UnsupportedArgs { index, kind: "BigUnsigned" });
+        }
+
+        let arrays = matches!(DB::flavor(), Flavor::PostgreSQL);
+
+        if let Some(index) = values.iter().position(|v| matches!(v, Value::Array(v) if !arrays || !is_bindable_array(v))) {
+            return Err(Error::UnsupportedArgs { index, kind: "Array" });
+        }
+
+        Ok(Self {
+            sql,
+            values,
+            database: PhantomData,
+        })
+    }
+
+    // 返回编译后的 SQL
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    // 返回按顺序绑定的参数
+    pub fn values(&self) -> &[Value] {
+        &self.values
+    }
+
+    // 返回绑定了所有参数的查询
+    pub fn query(&self) -> Query<'_, DB, <DB as Database>::Arguments<'_>> {
+        self.values
+            .iter()
+            .fold(::sqlx::query(&self.sql), |query, value| DB::bind(query, value))
+    }
+
+    // 返回绑定了所有参数、结果映射为 O 的查询
+    pub fn query_as<O>(&self) -> QueryAs<'_, DB, O, <DB as Database>::Arguments<'_>>
+    where
+        O: for<'r> FromRow<'r, DB::Row>,
+    {
+        self.values
+            .iter()
+            .fold(::sqlx::query_as(&self.sql), |query, value| DB::bind_as(query, value))
+    }
+}
+
+// 判断数组能否绑定：非 NULL 元素的类型相同，不能嵌套数组，无符号整数不能超出 i64 的范围
+fn is_bindable_array(values: &[Value]) -> bool {
+    let mut elements = values.iter().filter(|v| !v.is_null());
+    let first = match elements.next() {
+        Some(first) => first,
+        None => return true,
+    };
+
+    values.iter().all(|v| match v {
+        Value::Array(_) => false,
+        Value::BigUnsigned(v) => i64::try_from(*v).is_ok(),
+        _ => true,
+    }) && elements.all(|v| v.kind() == first.kind())
+}
+
+// SqlxBuilder 为所有构造器提供生成 sqlx 查询的方法
+pub trait SqlxBuilder: Builder {
+    // 使用数据库对应的 Flavor 编译 SQL，并转换所有参数
+    fn build_sqlx<DB: SqlxDatabase>(&self) -> Result<SqlxQuery<DB>, Error> {
+        let (sql, args) = self.build_with_flavor(DB::flavor(), Vec::new())?;
+        SqlxQuery::new(sql, args)
+    }
+}
+
+impl<B: Builder + ?Sized> SqlxBuilder for B {}
+
+#[cfg(all(test, feature = "sqlx-sqlite"))]
+mod tests {
+    use super::*;
+    use crate::testing::RawBuilder;
+    use ::sqlx::{Connection, SqliteConnection};
+    use futures_executor::block_on;
+
+    #[test]
+    fn test_sqlite() {
+        block_on(async {
+            let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+            ::sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL, score REAL)")
+                .execute(&mut conn)
+                .await
+                .unwrap();
+
+            for (id, name, score) in [(1, "alice", Some(9.5)), (2, "bob", None)] {
+                let insert = RawBuilder::new(|args| {
+                    format!(
+                        "INSERT INTO users (id, name, score) VALUES ({}, {}, {})",
+                        args.add(id),
+                        args.add(name),
+                        args.add(score),
+                    )
+                });
+                let query = insert.build_sqlx::<::sqlx::Sqlite>().unwrap();
+                assert_eq!(query.sql(), "INSERT INTO users (id, name, score) VALUES (?, ?, ?)");
+                query.query().execute(&mut conn).await.unwrap();
+            }
+
+            let select = RawBuilder::new(|args| {
+                format!("SELECT name, score FROM users WHERE id >= {} ORDER BY id", args.add(1u8))
+            });
+            let query = select.build_sqlx::<::sqlx::Sqlite>().unwrap();
+            let rows: Vec<(String, Option<f64>)> = query.query_as().fetch_all(&mut conn).await.unwrap();
+            assert_eq!(rows, vec![("alice".to_string(), Some(9.5)), ("bob".to_string(), None)]);
+        });
+    }
+
+    #[test]
+    fn test_unsupported_args() {
+        let select = RawBuilder::new(|args| format!("SELECT {}", args.add(u64::MAX)));
+        assert!(matches!(
+            select.build_sqlx::<::sqlx::Sqlite>(),
+            Err(Error::UnsupportedArgs { index: 0, kind: "BigUnsigned" })
+        ));
+
+        let select = RawBuilder::new(|args| format!("SELECT {}", args.add(Value::array([1, 2]))));
+        assert!(matches!(
+            select.build_sqlx::<::sqlx::Sqlite>(),
+            Err(Error::UnsupportedArgs { index: 0, kind: "Array" })
+        ));
+    }
+}
@@ -1,5 +1,3 @@
-use std::io::{self, Write};
-
 // stringBuilder 结构体，用于构建字符串
 pub struct StringBuilder {
     buffer: String,
@@ -7,7 +5,7 @@ pub struct StringBuilder {
 
 impl StringBuilder {
     // 创建一个新的 StringBuilder 实例
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
             buffer: String::new(),
         }
@@ -23,7 +21,7 @@ impl StringBuilder {
     }
 
     // 写入字符串
-    fn write_string(&mut self, s: &str) {
+    pub(crate) fn write_string(&mut self, s: &str) {
         self.buffer.push_str(s);
     }
 
@@ -54,33 +52,12 @@ impl StringBuilder {
     }
 
     // 写入一个字符
-    fn write_rune(&mut self, r: char) {
+    pub(crate) fn write_rune(&mut self, r: char) {
         self.buffer.push(r);
     }
 
-    // 实现 io::Write trait，用于写入字节数据
-    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
-        let s = String::from_utf8_lossy(data);
-        self.buffer.push_str(&s);
-        Ok(data.len())
-    }
-
     // 返回缓冲区内容
-    fn to_string(&self) -> &str {
+    pub(crate) fn to_string(&self) -> &str {
         &self.buffer
     }
-
-    // 重置缓冲区
-    fn reset(&mut self) {
-        self.buffer.clear();
-    }
-
-    // 预分配缓冲区容量
-    // 扩展容量
-    fn grow(&mut self, n: usize) {
-        if n > self.buffer.capacity() - self.buffer.len() {
-            let new_capacity = 2 * self.buffer.capacity() + n;
-            self.buffer.reserve(new_capacity - self.buffer.len());
-        }
-    }
 }
@@ -0,0 +1,29 @@
+use crate::args::Args;
+use crate::builder::Builder;
+use crate::error::Error;
+use crate::flavor::Flavor;
+use crate::value::Value;
+
+// 由格式串和 Args 组成的构造器，用于在测试中执行任意语句
+pub(crate) struct RawBuilder {
+    format: String,
+    args: Args,
+}
+
+impl RawBuilder {
+    pub(crate) fn new(format: impl FnOnce(&mut Args) -> String) -> Self {
+        let mut args = Args::new();
+        let format = format(&mut args);
+        Self { format, args }
+    }
+}
+
+impl Builder for RawBuilder {
+    fn build_with_flavor(&self, flavor: Flavor, initial_arg: Vec<Value>) -> Result<(String, Vec<Value>), Error> {
+        self.args.compile_with_flavor(&self.format, flavor, initial_arg)
+    }
+
+    fn flavor(&self) -> Flavor {
+        self.args.flavor.clone()
+    }
+}
@@ -0,0 +1,317 @@
+// Value 是构造器中参数的统一表示
+// 参数在添加时转换为 Value，并保留原始类型直到绑定到驱动或内联到 SQL 中
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    TinyInt(i8),
+    SmallInt(i16),
+    Int(i32),
+    BigInt(i64),
+    TinyUnsigned(u8),
+    SmallUnsigned(u16),
+    Unsigned(u32),
+    BigUnsigned(u64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    #[cfg(feature = "chrono")]
+    ChronoDate(chrono::NaiveDate),
+    #[cfg(feature = "chrono")]
+    ChronoTime(chrono::NaiveTime),
+    #[cfg(feature = "chrono")]
+    ChronoDateTime(chrono::NaiveDateTime),
+    #[cfg(feature = "chrono")]
+    ChronoDateTimeUtc(chrono::DateTime<chrono::Utc>),
+    #[cfg(feature = "chrono")]
+    ChronoDateTimeWithTimeZone(chrono::DateTime<chrono::FixedOffset>),
+    #[cfg(feature = "time")]
+    TimeDate(time::Date),
+    #[cfg(feature = "time")]
+    TimeTime(time::Time),
+    #[cfg(feature = "time")]
+    TimeDateTime(time::PrimitiveDateTime),
+    #[cfg(feature = "time")]
+    TimeDateTimeWithTimeZone(time::OffsetDateTime),
+    #[cfg(feature = "uuid")]
+    Uuid(uuid::Uuid),
+    #[cfg(feature = "serde_json")]
+    Json(serde_json::Value),
+    #[cfg(feature = "rust_decimal")]
+    Decimal(rust_decimal::Decimal),
+    // 数组，整体绑定为一个参数，只有支持数组的驱动可以绑定，例如 PostgreSQL
+    Array(Vec<Value>),
+}
+
+impl Value {
+    // 判断是否为 NULL
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    // 返回类型的名称，用于错误消息
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Value::Null => "Null",
+            Value::Bool(_) => "Bool",
+            Value::TinyInt(_) => "TinyInt",
+            Value::SmallInt(_) => "SmallInt",
+            Value::Int(_) => "Int",
+            Value::BigInt(_) => "BigInt",
+            Value::TinyUnsigned(_) => "TinyUnsigned",
+            Value::SmallUnsigned(_) => "SmallUnsigned",
+            Value::Unsigned(_) => "Unsigned",
+            Value::BigUnsigned(_) => "BigUnsigned",
+            Value::Float(_) => "Float",
+            Value::Double(_) => "Double",
+            Value::String(_) => "String",
+            Value::Bytes(_) => "Bytes",
+            #[cfg(feature = "chrono")]
+            Value::ChronoDate(_) => "ChronoDate",
+            #[cfg(feature = "chrono")]
+            Value::ChronoTime(_) => "ChronoTime",
+            #[cfg(feature = "chrono")]
+            Value::ChronoDateTime(_) => "ChronoDateTime",
+            #[cfg(feature = "chrono")]
+            Value::ChronoDateTimeUtc(_) => "ChronoDateTimeUtc",
+            #[cfg(feature = "chrono")]
+            Value::ChronoDateTimeWithTimeZone(_) => "ChronoDateTimeWithTimeZone",
+            #[cfg(feature = "time")]
+            Value::TimeDate(_) => "TimeDate",
+            #[cfg(feature = "time")]
+            Value::TimeTime(_) => "TimeTime",
+            #[cfg(feature = "time")]
+            Value::TimeDateTime(_) => "TimeDateTime",
+            #[cfg(feature = "time")]
+            Value::TimeDateTimeWithTimeZone(_) => "TimeDateTimeWithTimeZone",
+            #[cfg(feature = "uuid")]
+            Value::Uuid(_) => "Uuid",
+            #[cfg(feature = "serde_json")]
+            Value::Json(_) => "Json",
+            #[cfg(feature = "rust_decimal")]
+            Value::Decimal(_) => "Decimal",
+            Value::Array(_) => "Array",
+        }
+    }
+
+    // 将多个值转换为数组
+    pub fn array<T: ToSqlValue>(values: impl IntoIterator<Item = T>) -> Value {
+        Value::Array(values.into_iter().map(ToSqlValue::to_sql_value).collect())
+    }
+
+    // 返回日期时间、UUID、JSON 和 Decimal 等扩展类型的文本形式，其他类型返回 None
+    // 内联到 SQL 以及驱动不支持对应类型时都使用这个文本
+    pub fn to_text(&self) -> Option<String> {
+        match self {
+            #[cfg(feature = "chrono")]
+            Value::ChronoDate(v) => Some(v.format("%Y-%m-%d").to_string()),
+            #[cfg(feature = "chrono")]
+            Value::ChronoTime(v) => Some(v.format("%H:%M:%S%.f").to_string()),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDateTime(v) => Some(v.format("%Y-%m-%d %H:%M:%S%.f").to_string()),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDateTimeUtc(v) => Some(v.format("%Y-%m-%d %H:%M:%S%.f%:z").to_string()),
+            #[cfg(feature = "chrono")]
+            Value::ChronoDateTimeWithTimeZone(v) => Some(v.format("%Y-%m-%d %H:%M:%S%.f%:z").to_string()),
+            #[cfg(feature = "time")]
+            Value::TimeDate(v) => Some(format_time_date(*v)),
+            #[cfg(feature = "time")]
+            Value::TimeTime(v) => Some(format_time_time(*v)),
+            #[cfg(feature = "time")]
+            Value::TimeDateTime(v) => Some(format!("{} {}", format_time_date(v.date()), format_time_time(v.time()))),
+            #[cfg(feature = "time")]
+            Value::TimeDateTimeWithTimeZone(v) => {
+                let (h, m, _) = v.offset().as_hms();
+                let sign = if v.offset().is_negative() { '-' } else { '+' };
+                Some(format!(
+                    "{} {}{}{:02}:{:02}",
+                    format_time_date(v.date()),
+                    format_time_time(v.time()),
+                    sign,
+                    h.unsigned_abs(),
+                    m.unsigned_abs()
+                ))
+            }
+            #[cfg(feature = "uuid")]
+            Value::Uuid(v) => Some(v.hyphenated().to_string()),
+            #[cfg(feature = "serde_json")]
+            Value::Json(v) => Some(v.to_string()),
+            #[cfg(feature = "rust_decimal")]
+            Value::Decimal(v) => Some(v.to_string()),
+            _ => None,
+        }
+    }
+}
+
+// 将 time 的日期格式化为 YYYY-MM-DD
+#[cfg(feature = "time")]
+fn format_time_date(v: time::Date) -> String {
+    format!("{:04}-{:02}-{:02}", v.year(), u8::from(v.month()), v.day())
+}
+
+// 将 time 的时间格式化为 HH:MM:SS，有小数秒时保留到微秒
+#[cfg(feature = "time")]
+fn format_time_time(v: time::Time) -> String {
+    use std::fmt::Write;
+
+    let mut s = format!("{:02}:{:02}:{:02}", v.hour(), v.minute(), v.second());
+
+    if v.microsecond() != 0 {
+        let _ = write!(s, ".{:06}", v.microsecond());
+    }
+
+    s
+}
+
+// ToSqlValue 将 Rust 值转换为 Value
+// 构造器和 Args 接受所有实现了 ToSqlValue 的类型
+pub trait ToSqlValue {
+    fn to_sql_value(self) -> Value;
+}
+
+impl ToSqlValue for Value {
+    fn to_sql_value(self) -> Value {
+        self
+    }
+}
+
+// 为可以直接放入 Value 的类型实现 ToSqlValue
+macro_rules! impl_to_sql_value {
+    ($($t:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl ToSqlValue for $t {
+                fn to_sql_value(self) -> Value {
+                    Value::$variant(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_to_sql_value!(
+    bool => Bool,
+    i8 => TinyInt,
+    i16 => SmallInt,
+    i32 => Int,
+    i64 => BigInt,
+    u8 => TinyUnsigned,
+    u16 => SmallUnsigned,
+    u32 => Unsigned,
+    u64 => BigUnsigned,
+    f32 => Float,
+    f64 => Double,
+    String => String,
+    Vec<u8> => Bytes,
+);
+
+#[cfg(feature = "chrono")]
+impl_to_sql_value!(
+    chrono::NaiveDate => ChronoDate,
+    chrono::NaiveTime => ChronoTime,
+    chrono::NaiveDateTime => ChronoDateTime,
+    chrono::DateTime<chrono::Utc> => ChronoDateTimeUtc,
+    chrono::DateTime<chrono::FixedOffset> => ChronoDateTimeWithTimeZone,
+);
+
+#[cfg(feature = "time")]
+impl_to_sql_value!(
+    time::Date => TimeDate,
+    time::Time => TimeTime,
+    time::PrimitiveDateTime => TimeDateTime,
+    time::OffsetDateTime => TimeDateTimeWithTimeZone,
+);
+
+#[cfg(feature = "uuid")]
+impl_to_sql_value!(uuid::Uuid => Uuid);
+
+#[cfg(feature = "serde_json")]
+impl_to_sql_value!(serde_json::Value => Json);
+
+#[cfg(feature = "rust_decimal")]
+impl_to_sql_value!(rust_decimal::Decimal => Decimal);
+
+// isize 和 usize 按 64 位整数保存
+impl ToSqlValue for isize {
+    fn to_sql_value(self) -> Value {
+        Value::BigInt(self as i64)
+    }
+}
+
+impl ToSqlValue for usize {
+    fn to_sql_value(self) -> Value {
+        Value::BigUnsigned(self as u64)
+    }
+}
+
+impl ToSqlValue for char {
+    fn to_sql_value(self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl ToSqlValue for &str {
+    fn to_sql_value(self) -> Value {
+        Value::String(self.to_string())
+    }
+}
+
+impl ToSqlValue for &[u8] {
+    fn to_sql_value(self) -> Value {
+        Value::Bytes(self.to_vec())
+    }
+}
+
+// 空元组表示 NULL
+impl ToSqlValue for () {
+    fn to_sql_value(self) -> Value {
+        Value::Null
+    }
+}
+
+// None 转换为 NULL
+impl<T: ToSqlValue> ToSqlValue for Option<T> {
+    fn to_sql_value(self) -> Value {
+        self.map_or(Value::Null, ToSqlValue::to_sql_value)
+    }
+}
+
+// 引用会克隆后转换
+impl<T: ToSqlValue + Clone> ToSqlValue for &T {
+    fn to_sql_value(self) -> Value {
+        self.clone().to_sql_value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_sql_value() {
+        assert_eq!(1i8.to_sql_value(), Value::TinyInt(1));
+        assert_eq!(1u64.to_sql_value(), Value::BigUnsigned(1));
+        assert_eq!((-1isize).to_sql_value(), Value::BigInt(-1));
+        assert_eq!(1usize.to_sql_value(), Value::BigUnsigned(1));
+        assert_eq!(1.5f32.to_sql_value(), Value::Float(1.5));
+        assert_eq!('a'.to_sql_value(), Value::String("a".to_string()));
+        assert_eq!("a".to_sql_value(), Value::String("a".to_string()));
+        assert_eq!((&[1u8, 2][..]).to_sql_value(), Value::Bytes(vec![1, 2]));
+        assert_eq!(().to_sql_value(), Value::Null);
+        assert_eq!(None::<i32>.to_sql_value(), Value::Null);
+        assert_eq!(Some(&"a".to_string()).to_sql_value(), Value::String("a".to_string()));
+        assert_eq!(Value::array([Some(1), None]), Value::Array(vec![Value::Int(1), Value::Null]));
+    }
+
+    #[test]
+    fn test_kind_and_text() {
+        assert!(Value::Null.is_null());
+        assert!(!Value::Int(0).is_null());
+        assert_eq!(Value::Null.kind(), "Null");
+        assert_eq!(Value::Double(f64::NAN).kind(), "Double");
+        assert_eq!(Value::array([1]).kind(), "Array");
+
+        assert_eq!(Value::Int(1).to_text(), None);
+        assert_eq!(Value::String("a".to_string()).to_text(), None);
+    }
+}
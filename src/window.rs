@@ -0,0 +1,298 @@
+use crate::args::{escape, Args};
+use crate::error::Error;
+use crate::expr::{write_list, Expr};
+use crate::stringbuilder::StringBuilder;
+
+// 窗口框架的单位
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameUnit {
+    Rows,
+    Range,
+}
+
+// 窗口框架的边界
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameBound {
+    // 作为起点时为 UNBOUNDED PRECEDING，作为终点时为 UNBOUNDED FOLLOWING
+    Unbounded,
+    // n PRECEDING
+    Preceding(u64),
+    // CURRENT ROW
+    Current,
+    // n FOLLOWING
+    Following(u64),
+}
+
+// 窗口框架，例如 ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frame {
+    pub unit: FrameUnit,
+    pub start: FrameBound,
+    pub end: FrameBound,
+}
+
+// 排序方向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Order {
+    Asc,
+    Desc,
+}
+
+impl Order {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Order::Asc => "ASC",
+            Order::Desc => "DESC",
+        }
+    }
+}
+
+// Window 描述窗口函数的 OVER 子句
+// partition_by 和 order_by 接受列名，例如 "dept"、"salary DESC"，列名按需加引号
+// 需要任意表达式时使用 partition_by_exprs、order_by_expr
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Window {
+    // 引用 WINDOW 子句中定义的命名窗口
+    pub name: Option<String>,
+    pub partition_by: Vec<Expr>,
+    // 排序的表达式和方向，没有方向时使用数据库的默认顺序
+    pub order_by: Vec<(Expr, Option<Order>)>,
+    pub frame: Option<Frame>,
+}
+
+impl Window {
+    // 创建一个空的窗口，渲染为 OVER ()
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // 引用命名窗口，没有其他设置时渲染为 OVER name，否则渲染为 OVER (name ...)
+    pub fn named(name: &str) -> Self {
+        Self {
+            name: Some(name.to_string()),
+            ..Self::default()
+        }
+    }
+
+    // 添加 PARTITION BY 的列
+    pub fn partition_by<S: AsRef<str>>(mut self, columns: impl IntoIterator<Item = S>) -> Self {
+        self.partition_by.extend(columns.into_iter().map(|c| Expr::col(c.as_ref())));
+        self
+    }
+
+    // 添加 PARTITION BY 的表达式，注意字符串会绑定为参数值
+    pub fn partition_by_exprs<E: Into<Expr>>(mut self, exprs: impl IntoIterator<Item = E>) -> Self {
+        self.partition_by.extend(exprs.into_iter().map(Into::into));
+        self
+    }
+
+    // 添加 ORDER BY 的列，列名之后可以有 ASC 或 DESC，例如 "salary DESC"
+    pub fn order_by<S: AsRef<str>>(mut self, columns: impl IntoIterator<Item = S>) -> Self {
+        self.order_by.extend(columns.into_iter().map(|c| order_column(c.as_ref())));
+        self
+    }
+
+    // 添加按 expr 排序的 ORDER BY 项
+    pub fn order_by_expr(mut self, expr: impl Into<Expr>, order: Order) -> Self {
+        self.order_by.push((expr.into(), Some(order)));
+        self
+    }
+
+    // 设置 ROWS BETWEEN start AND end
+    pub fn rows_between(mut self, start: FrameBound, end: FrameBound) -> Self {
+        self.frame = Some(Frame {
+            unit: FrameUnit::Rows,
+            start,
+            end,
+        });
+        self
+    }
+
+    // 设置 RANGE BETWEEN start AND end
+    pub fn range_between(mut self, start: FrameBound, end: FrameBound) -> Self {
+        self.frame = Some(Frame {
+            unit: FrameUnit::Range,
+            start,
+            end,
+        });
+        self
+    }
+
+    // 判断是否只引用了命名窗口
+    pub fn is_reference(&self) -> bool {
+        self.name.is_some() && self.partition_by.is_empty() && self.order_by.is_empty() && self.frame.is_none()
+    }
+
+    // 按 args 的 Flavor 渲染不带括号的窗口定义，参数添加到 args 中
+    // 用于 OVER (...)，也可以用于 WINDOW name AS (...) 子句
+    pub fn render(&self, args: &mut Args) -> Result<String, Error> {
+        let mut buf = StringBuilder::new();
+
+        if let Some(name) = &self.name {
+            buf.write_leading_string(&escape(name));
+        }
+
+        if !self.partition_by.is_empty() {
+            buf.write_leading_string("PARTITION BY ");
+            write_list(&mut buf, args, &self.partition_by)?;
+        }
+
+        if !self.order_by.is_empty() {
+            buf.write_leading_string("ORDER BY ");
+
+            for (i, (expr, order)) in self.order_by.iter().enumerate() {
+                if i > 0 {
+                    buf.write_string(", ");
+                }
+
+                expr.write_to(&mut buf, args)?;
+
+                if let Some(order) = order {
+                    buf.write_rune(' ');
+                    buf.write_string(order.as_str());
+                }
+            }
+        }
+
+        if let Some(frame) = &self.frame {
+            let unit = match frame.unit {
+                FrameUnit::Rows => "ROWS",
+                FrameUnit::Range => "RANGE",
+            };
+            let frame = format!(
+                "{} BETWEEN {} AND {}",
+                unit,
+                bound(frame.start, "PRECEDING"),
+                bound(frame.end, "FOLLOWING")
+            );
+            buf.write_leading_string(&frame);
+        }
+
+        Ok(buf.to_string().to_string())
+    }
+}
+
+// 拆分列名之后的排序方向
+fn order_column(column: &str) -> (Expr, Option<Order>) {
+    let column = column.trim();
+
+    if let Some((name, direction)) = column.rsplit_once(char::is_whitespace) {
+        let order = match direction {
+            d if d.eq_ignore_ascii_case("ASC") => Some(Order::Asc),
+            d if d.eq_ignore_ascii_case("DESC") => Some(Order::Desc),
+            _ => None,
+        };
+
+        if order.is_some() {
+            return (Expr::col(name.trim_end()), order);
+        }
+    }
+
+    (Expr::col(column), None)
+}
+
+// 返回框架边界的写法，unbounded 为 UNBOUNDED 之后的方向
+fn bound(bound: FrameBound, unbounded: &str) -> String {
+    match bound {
+        FrameBound::Unbounded => format!("UNBOUNDED {}", unbounded),
+        FrameBound::Preceding(n) => format!("{} PRECEDING", n),
+        FrameBound::Current => "CURRENT ROW".to_string(),
+        FrameBound::Following(n) => format!("{} FOLLOWING", n),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::BuildMode;
+    use crate::dialect::Feature;
+    use crate::flavor::Flavor;
+    use crate::func;
+    use crate::value::Value;
+    use FrameBound::{Current, Preceding, Unbounded};
+
+    #[test]
+    fn test_window() {
+        let rank = Expr::func("ROW_NUMBER", Vec::<Expr>::new()).over(
+            Window::new()
+                .partition_by(["dept"])
+                .order_by(["salary DESC"])
+                .rows_between(Unbounded, Current),
+        );
+        let total = Expr::func("SUM", [Expr::col("salary")])
+            .filter(Expr::col("active").eq(true))
+            .over(Window::named("w"));
+        let paid = Expr::func("COUNT", [Expr::col("*")])
+            .filter(Expr::col("amount").gt(0))
+            .over(Window::named("w").range_between(Preceding(3), Unbounded));
+
+        let mut args = Args::new();
+        let format = format!(
+            "SELECT {}, {}, {} FROM employees",
+            args.add_expr(rank),
+            args.add_expr(total),
+            args.add_expr(paid)
+        );
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            "SELECT ROW_NUMBER() OVER (PARTITION BY dept ORDER BY salary DESC ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW), \
+             SUM(salary) FILTER (WHERE active = $1) OVER w, \
+             COUNT(*) FILTER (WHERE amount > $2) OVER (w RANGE BETWEEN 3 PRECEDING AND UNBOUNDED FOLLOWING) FROM employees"
+        );
+        assert_eq!(values, vec![Value::Bool(true), Value::Int(0)]);
+
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::MySQL, Vec::new()).unwrap();
+        assert!(sql.contains("SUM(CASE WHEN active = ? THEN salary END) OVER w"));
+        assert!(sql.contains("COUNT(CASE WHEN amount > ? THEN 1 END) OVER (w RANGE"));
+
+        args.mode = BuildMode::Strict;
+        let err = args.compile_with_flavor(&format, Flavor::MySQL, Vec::new()).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedFeature { feature: Feature::AggregateFilter, .. }));
+
+        let err = args.compile_with_flavor(&format, Flavor::CQL, Vec::new()).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedFeature { feature: Feature::WindowFunction, .. }));
+
+        let err = args.compile_with_flavor(&format, Flavor::MySQL57, Vec::new()).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedFeature { feature: Feature::WindowFunction, .. }));
+        assert!(Flavor::MySQL.supports(Feature::WindowFunction));
+        assert_eq!("mysql5.7".parse::<Flavor>().unwrap(), Flavor::MySQL57);
+
+        let names = func::string_agg(Expr::col("name"), ",").filter(Expr::col("active").eq(true));
+        let mut args = Args::new();
+        let format = format!("SELECT {} FROM employees", args.add_expr(names));
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::MySQL, Vec::new()).unwrap();
+        assert!(!sql.contains("FILTER"), "{}", sql);
+
+        let active = Expr::col("active").filter(Expr::col("amount").gt(0));
+        let mut args = Args::new();
+        let format = format!("SELECT {} FROM employees", args.add_expr(active));
+        let err = args.compile_with_flavor(&format, Flavor::MySQL, Vec::new()).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedFeature { feature: Feature::AggregateFilter, .. }));
+    }
+
+    #[test]
+    fn test_window_columns() {
+        let window = Window::new()
+            .partition_by(["dept", "order"])
+            .order_by(["hired_at", "salary desc"])
+            .order_by_expr(Expr::col("bonus") + Expr::col("salary"), Order::Asc);
+        let mut args = Args::new();
+        let format = args.add_expr(Expr::func("RANK", Vec::<Expr>::new()).over(window.clone()));
+
+        let (sql, values) = args.compile_with_flavor(&format, Flavor::PostgreSQL, Vec::new()).unwrap();
+        assert_eq!(
+            sql,
+            "RANK() OVER (PARTITION BY dept, \"order\" ORDER BY hired_at, salary DESC, bonus + salary ASC)"
+        );
+        assert!(values.is_empty());
+
+        let window = Window::new().partition_by_exprs([Expr::func("YEAR", [Expr::col("hired_at")])]);
+        let mut args = Args::new();
+        let format = args.add_expr(Expr::func("COUNT", [Expr::col("*")]).over(window));
+        let (sql, _) = args.compile_with_flavor(&format, Flavor::MySQL, Vec::new()).unwrap();
+        assert_eq!(sql, "COUNT(*) OVER (PARTITION BY YEAR(hired_at))");
+    }
+
+}